@@ -16,6 +16,13 @@ pub enum {} {{
 r#"impl Display for {} {{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
         write!(f, "{{}}", match self {{
+"#,
+    enum_name);
+    let mut from_str_out = format!(
+r#"impl FromStr for {} {{
+    type Err = UnknownIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {{
+        Ok(match s.strip_prefix("minecraft:").unwrap_or(s) {{
 "#,
     enum_name);
     for item in fs::read_to_string(items).unwrap().split("\r\n") {
@@ -32,15 +39,28 @@ r#"            {}::{} => "{}",
 "#,
             enum_name, ident, &item[10..]
         ).unwrap();
+        writeln!(
+            from_str_out,
+r#"            "{}" => {}::{},"#,
+            &item[10..], enum_name, ident
+        ).unwrap();
     }
     out.write_str("}\n").unwrap();
     fmt_out.write_str(
 r#"        })
     }
 }
+"#
+    ).unwrap();
+    from_str_out.write_str(
+r#"            _ => return Err(UnknownIdError)
+        })
+    }
+}
 "#
     ).unwrap();
     out.write_str(&fmt_out).unwrap();
+    out.write_str(&from_str_out).unwrap();
     fs::write(out_dir.join(out_file), out).unwrap();
 }
 
@@ -1,9 +1,19 @@
-use std::{fmt::{Display, Write}, path::{Path, PathBuf}};
+use std::{borrow::Cow, fmt::{Display, Write}, ops::{RangeFrom, RangeInclusive, RangeToInclusive}, path::{Path, PathBuf}};
 use crate::minecraft::Entity;
+use nbt::Nbt;
 use serde::{Serialize, Serializer, ser::SerializeMap};
 
-/// Represents an identifier, of the form `namespace:folders.../id`
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub mod nbt;
+pub mod text;
+mod parse;
+
+pub use parse::ParseError;
+/// The old name of [`ParseError`], from when only selectors could be parsed
+pub type SelectorParseError = ParseError;
+
+/// Represents an identifier, of the form `namespace:folders.../id`.
+/// Two identifiers are equal if they render the same, so `foo:bar/baz` with folders `["bar"]` equals one parsed from a string.
+#[derive(Debug, Clone, Copy)]
 pub struct Identifier<'a, 'b> {
     /// The namespace the identifier is in
     pub namespace: &'a str,
@@ -31,17 +41,74 @@ impl<'a, 'b> Identifier<'a, 'b> {
         path.set_extension(extension);
         path
     }
+    /// Parse an identifier, e.g `foo:bar/baz`. If the namespace is left out it defaults to `minecraft`.
+    /// The folders are kept in the id, since they can't be borrowed as a slice.
+    /// Returns `None` if the identifier contains invalid characters.
+    /// ```
+    /// # use copper::{id, core::Identifier};
+    /// assert_eq!(Identifier::parse("foo:bar/baz"), Some(id!(foo:bar/baz)));
+    /// assert_eq!(Identifier::parse("stone"), Some(id!(stone)));
+    /// assert_eq!(Identifier::parse("Foo:bar"), None);
+    /// ```
+    pub fn parse(id: &'a str) -> Option<Self> {
+        let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+        let valid = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.');
+        if namespace.is_empty() || !namespace.chars().all(valid)
+            || path.is_empty() || !path.chars().all(|c| valid(c) || c == '/')
+            || path.split('/').any(str::is_empty) {
+            return None;
+        }
+        Some(Self {namespace, folders: &[], id: path})
+    }
+    fn parts(&self) -> impl Iterator<Item = &str> {
+        self.folders.iter().copied().chain(std::iter::once(self.id)).flat_map(|part| part.split('/'))
+    }
+}
+impl PartialEq for Identifier<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.namespace == other.namespace && self.parts().eq(other.parts())
+    }
+}
+impl Eq for Identifier<'_, '_> {}
+impl Display for Identifier<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.namespace)?;
+        for folder in self.folders {
+            write!(f, "{}/", folder)?;
+        }
+        write!(f, "{}", self.id)
+    }
 }
 impl Serialize for Identifier<'_, '_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer {
-        let mut out = String::from(self.namespace);
-        for folder in self.folders {
-            write!(out, "{}/", folder).unwrap();
-        }
-        write!(out, "{}", self.id).unwrap();
-        serializer.serialize_str(&out)
+        serializer.collect_str(self)
+    }
+}
+
+/// An owned [`Identifier`], for identifiers which are kept after the ones they were made from are gone.
+/// The folders are split out of the id, so one made from a parsed identifier has the same parts as one made with [`id!`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub (crate) struct OwnedIdentifier {
+    pub (crate) namespace: String,
+    pub (crate) folders: Vec<String>,
+    pub (crate) id: String
+}
+impl OwnedIdentifier {
+    /// Borrow the folders, to build an [`Identifier`] from `self` with [`OwnedIdentifier::identifier`]
+    pub (crate) fn folders(&self) -> Vec<&str> {
+        self.folders.iter().map(String::as_str).collect()
+    }
+    pub (crate) fn identifier<'a, 'b>(&'a self, folders: &'a [&'b str]) -> Identifier<'a, 'b> {
+        Identifier {namespace: &self.namespace, folders, id: &self.id}
+    }
+}
+impl From<Identifier<'_, '_>> for OwnedIdentifier {
+    fn from(identifier: Identifier<'_, '_>) -> Self {
+        let mut parts: Vec<_> = identifier.parts().map(String::from).collect();
+        let id = parts.pop().unwrap();
+        Self {namespace: identifier.namespace.to_string(), folders: parts, id}
     }
 }
 
@@ -90,7 +157,7 @@ impl Display for SelectorType {
 pub enum SelectorSort {
     #[doc = "Represents `sort=nearest`"] Nearest,
     #[doc = "Represents `sort=furthest`"] Furthest,
-    #[doc = "Represents `sort=arbritrary`"] Arbritrary,
+    #[doc = "Represents `sort=arbitrary`"] Arbritrary,
     #[doc = "Represents `sort=random`"] Random
 }
 impl Display for SelectorSort {
@@ -99,7 +166,7 @@ impl Display for SelectorSort {
         write!(f,"{}",match self {
             Nearest => "nearest",
             Furthest => "furthest",
-            Arbritrary => "arbritrary",
+            Arbritrary => "arbitrary",
             Random => "random"
         })
     }
@@ -126,33 +193,113 @@ impl Display for GameMode {
     }
 }
 
+/// How an advancement is checked in a selector's `advancements=` argument
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SelectorAdvancement<'a> {
+    /// Checks if the whole advancement is complete, e.g `foo:bar=true`
+    Complete(bool),
+    /// Checks each criterion in the form `(criterion, completeness)`, e.g `foo:bar={baz=true}`
+    Criteria(Vec<(&'a str, bool)>)
+}
+impl Display for SelectorAdvancement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Complete(complete) => write!(f, "{}", complete),
+            Self::Criteria(criteria) => {
+                f.write_char('{')?;
+                for (i, (criterion, complete)) in criteria.iter().enumerate() {
+                    if i != 0 {f.write_char(',')?;}
+                    write!(f, "{}={}", criterion, complete)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
 /// Represents a selector.
 /// Create a selector using one of the `at_` functions, like `at_s()`.
 /// Then modify it using the builder pattern. Each attribute for selectors has a method.
 /// Since many selector attributes have an optional `!`, these are represented with tuples, the second element being positiveness.
-/// Examples:
-/// `at_a().tag("foo").game_mode((GameMode::Creative, true))` == `@a[tag=foo,gamemode=creative]`
-/// `at_e().type("cow").sort(SelectorSort::Nearest).limit(1)` == `@e[type=cow,sort=nearest,limit=1]`
-/// `at_s()` == `@s`
-/// `at_a().game_mode((GameMode::Spectator, false))` == `@a[gamemode=!spectator]`
-#[derive(Default, Eq, PartialEq, Copy, Clone, Debug)]
+/// Attributes which can be repeated (like `tag=`) are stored in a `Vec`, and their methods add to it.
+/// Ranges are [`NumberRange`]s, so their methods take a rust range or a single number.
+/// ```
+/// # use copper::{prelude::*, core::{GameMode, SelectorSort}};
+/// assert_eq!(at_a().tag("foo", true).game_mode(GameMode::Creative, true).to_string(), "@a[gamemode=creative,tag=foo]");
+/// assert_eq!(at_e().entity(Entity::Cow, true).distance(..=5.0).sort(SelectorSort::Nearest).limit(1).to_string(),
+///     "@e[distance=..5,limit=1,sort=nearest,type=cow]");
+/// assert_eq!(at_a().score("kills", 1..).tag("", false).to_string(), "@a[scores={kills=1..},tag=!]");
+/// assert_eq!(at_s().to_string(), "@s");
+/// ```
+#[derive(Default, PartialEq, Clone, Debug)]
 pub struct Selector<'a> {
     sel: SelectorType,
+    #[doc = "Represents `x=`"] pub x: Option<f64>,
+    #[doc = "Represents `y=`"] pub y: Option<f64>,
+    #[doc = "Represents `z=`"] pub z: Option<f64>,
+    #[doc = "Represents `distance=`"] pub distance: Option<NumberRange<f64>>,
+    #[doc = "Represents `dx=`"] pub dx: Option<f64>,
+    #[doc = "Represents `dy=`"] pub dy: Option<f64>,
+    #[doc = "Represents `dz=`"] pub dz: Option<f64>,
+    #[doc = "Represents `scores=`, in the form `(objective, range)`"] pub scores: Vec<(&'a str, NumberRange<i64>)>,
     #[doc = "Represents `limit=`"] pub limit: Option<u64>,
     #[doc = "Represents `sort=`"] pub sort: Option<SelectorSort>,
-    #[doc = "Represents `level=`"] pub level: Option<(u64, u64)>,
+    #[doc = "Represents `level=`"] pub level: Option<NumberRange<u64>>,
     #[doc = "Represents `gamemode=`"] pub game_mode: Option<(GameMode, bool)>,
-    #[doc = "Represents `name=`"] pub name: Option<(&'a str, bool)>,
-    #[doc = "Represents `x_rotation=`"] pub x_rot: Option<(u64, u64)>,
-    #[doc = "Represents `y_rotation=`"] pub y_rot: Option<(u64, u64)>,
-    #[doc = "Represents `type=`"] pub ty: Option<(Entity, bool)>,
-    #[doc = "Represents `tag=`"] pub tag: Option<(&'a str, bool)>,
-    // TODO: Add other complex stuff
+    #[doc = "Represents `team=`. An empty team matches entities on no team (or any team, if negated)"] pub team: Option<(&'a str, bool)>,
+    #[doc = "Represents each `name=`. Names which had escapes when parsed are owned"] pub names: Vec<(Cow<'a, str>, bool)>,
+    #[doc = "Represents `x_rotation=`"] pub x_rot: Option<NumberRange<f64>>,
+    #[doc = "Represents `y_rotation=`"] pub y_rot: Option<NumberRange<f64>>,
+    #[doc = "Represents each `type=`"] pub types: Vec<(Entity, bool)>,
+    #[doc = "Represents each `tag=`. An empty tag matches entities with no tags (or any tag, if negated)"] pub tags: Vec<(&'a str, bool)>,
+    #[doc = "Represents each `nbt=`"] pub nbt: Vec<(Nbt, bool)>,
+    #[doc = "Represents `advancements=`"] pub advancements: Vec<(Identifier<'a, 'a>, SelectorAdvancement<'a>)>,
+    #[doc = "Represents each `predicate=`"] pub predicates: Vec<(Identifier<'a, 'a>, bool)>
 }
 impl<'a> Selector<'a> {
     fn new(sel: SelectorType) -> Self {
         Self {sel, ..Self::default()}
     }
+    /// Sets the `x` of this selector.
+    pub fn x(mut self, x: f64) -> Self {
+        self.x = Some(x);
+        self
+    }
+    /// Sets the `y` of this selector.
+    pub fn y(mut self, y: f64) -> Self {
+        self.y = Some(y);
+        self
+    }
+    /// Sets the `z` of this selector.
+    pub fn z(mut self, z: f64) -> Self {
+        self.z = Some(z);
+        self
+    }
+    /// Sets the `distance` of this selector.
+    pub fn distance(mut self, distance: impl Into<NumberRange<f64>>) -> Self {
+        self.distance = Some(distance.into());
+        self
+    }
+    /// Sets the `dx` of this selector.
+    pub fn dx(mut self, dx: f64) -> Self {
+        self.dx = Some(dx);
+        self
+    }
+    /// Sets the `dy` of this selector.
+    pub fn dy(mut self, dy: f64) -> Self {
+        self.dy = Some(dy);
+        self
+    }
+    /// Sets the `dz` of this selector.
+    pub fn dz(mut self, dz: f64) -> Self {
+        self.dz = Some(dz);
+        self
+    }
+    /// Adds a score to the `scores` of this selector.
+    pub fn score(mut self, objective: &'a str, range: impl Into<NumberRange<i64>>) -> Self {
+        self.scores.push((objective, range.into()));
+        self
+    }
     /// Sets the `limit` of this selector.
     pub fn limit(mut self, limit: u64) -> Self {
         self.limit = Some(limit);
@@ -163,9 +310,14 @@ impl<'a> Selector<'a> {
         self.sort = Some(sort);
         self
     }
+    /// Sets the `level` of this selector to `min..=max`.
+    #[deprecated(note = "use `level_range`, which also takes open-ended ranges")]
+    pub fn level(self, min: u64, max: u64) -> Self {
+        self.level_range(min..=max)
+    }
     /// Sets the `level` of this selector.
-    pub fn level(mut self, min: u64, max: u64) -> Self {
-        self.level = Some((min, max));
+    pub fn level_range(mut self, level: impl Into<NumberRange<u64>>) -> Self {
+        self.level = Some(level.into());
         self
     }
     /// Sets the `game_mode` of this selector.
@@ -173,29 +325,59 @@ impl<'a> Selector<'a> {
         self.game_mode = Some((game_mode, positive));
         self
     }
-    /// Sets the `name` of this selector.
-    pub fn name(mut self, name: &'a str, positive: bool) -> Self {
-        self.name = Some((name, positive));
+    /// Sets the `team` of this selector.
+    pub fn team(mut self, team: &'a str, positive: bool) -> Self {
+        self.team = Some((team, positive));
+        self
+    }
+    /// Adds a `name` to this selector. Call it again to add more names, e.g. several negated ones.
+    pub fn name(mut self, name: impl Into<Cow<'a, str>>, positive: bool) -> Self {
+        self.names.push((name.into(), positive));
         self
     }
+    /// Sets the `x_rot` of this selector to `min..=max`.
+    #[deprecated(note = "use `x_rotation`, which also takes negative and open-ended ranges")]
+    pub fn x_rot(self, min: u64, max: u64) -> Self {
+        self.x_rotation(min as f64..=max as f64)
+    }
     /// Sets the `x_rot` of this selector.
-    pub fn x_rot(mut self, min: u64, max: u64) -> Self {
-        self.x_rot = Some((min, max));
+    pub fn x_rotation(mut self, x_rot: impl Into<NumberRange<f64>>) -> Self {
+        self.x_rot = Some(x_rot.into());
         self
     }
+    /// Sets the `y_rot` of this selector to `min..=max`.
+    #[deprecated(note = "use `y_rotation`, which also takes negative and open-ended ranges")]
+    pub fn y_rot(self, min: u64, max: u64) -> Self {
+        self.y_rotation(min as f64..=max as f64)
+    }
     /// Sets the `y_rot` of this selector.
-    pub fn y_rot(mut self, min: u64, max: u64) -> Self {
-        self.y_rot = Some((min, max));
+    pub fn y_rotation(mut self, y_rot: impl Into<NumberRange<f64>>) -> Self {
+        self.y_rot = Some(y_rot.into());
         self
     }
-    /// Sets the `entity` of this selector.
+    /// Adds an `entity` type to this selector. Call it again to add more types, e.g. several negated ones.
     pub fn entity(mut self, ty: Entity, positive: bool) -> Self {
-        self.ty = Some((ty, positive));
+        self.types.push((ty, positive));
         self
     }
-    /// Sets the `tag` of this selector.
+    /// Adds a `tag` to this selector. Call it again to add more tags.
     pub fn tag(mut self, tag: &'a str, positive: bool) -> Self {
-        self.tag = Some((tag, positive));
+        self.tags.push((tag, positive));
+        self
+    }
+    /// Adds an `nbt` check to this selector.
+    pub fn nbt(mut self, nbt: Nbt, positive: bool) -> Self {
+        self.nbt.push((nbt, positive));
+        self
+    }
+    /// Adds an advancement to the `advancements` of this selector.
+    pub fn advancement(mut self, advancement: Identifier<'a, 'a>, check: SelectorAdvancement<'a>) -> Self {
+        self.advancements.push((advancement, check));
+        self
+    }
+    /// Adds a `predicate` to this selector.
+    pub fn predicate(mut self, predicate: Identifier<'a, 'a>, positive: bool) -> Self {
+        self.predicates.push((predicate, positive));
         self
     }
 }
@@ -204,27 +386,102 @@ impl Display for Selector<'_> {
         fn pos(positive: bool) -> &'static str {
             if positive {""} else {"!"}
         }
-        let list_start;
-        let list_end;
-        if self == &Self::new(self.sel) {
-            list_start = ""; list_end = "";
-        } else {
-            list_start = "["; list_end = "]";
+        fn quote(name: &str) -> String {
+            if name.chars().all(parse::is_unquoted) {
+                return name.to_string();
+            }
+            let quote = if name.contains('"') && !name.contains('\'') {'\''} else {'"'};
+            let mut quoted = String::from(quote);
+            for c in name.chars() {
+                if c == quote || c == '\\' {quoted.push('\\');}
+                quoted.push(c);
+            }
+            quoted.push(quote);
+            quoted
+        }
+        let mut args = Vec::new();
+        if let Some(x) = self.x {args.push(format!("x={}", x));}
+        if let Some(y) = self.y {args.push(format!("y={}", y));}
+        if let Some(z) = self.z {args.push(format!("z={}", z));}
+        if let Some(distance) = self.distance {args.push(format!("distance={}", distance));}
+        if let Some(dx) = self.dx {args.push(format!("dx={}", dx));}
+        if let Some(dy) = self.dy {args.push(format!("dy={}", dy));}
+        if let Some(dz) = self.dz {args.push(format!("dz={}", dz));}
+        if !self.scores.is_empty() {
+            let scores: Vec<_> = self.scores.iter().map(|(objective, range)| format!("{}={}", objective, range)).collect();
+            args.push(format!("scores={{{}}}", scores.join(",")));
+        }
+        if let Some(limit) = self.limit {args.push(format!("limit={}", limit));}
+        if let Some(sort) = self.sort {args.push(format!("sort={}", sort));}
+        if let Some(level) = self.level {args.push(format!("level={}", level));}
+        if let Some((mode, positive)) = self.game_mode {args.push(format!("gamemode={}{}", pos(positive), mode));}
+        if let Some((team, positive)) = self.team {args.push(format!("team={}{}", pos(positive), team));}
+        for (name, positive) in &self.names {args.push(format!("name={}{}", pos(*positive), quote(name)));}
+        if let Some(x_rot) = self.x_rot {args.push(format!("x_rotation={}", x_rot));}
+        if let Some(y_rot) = self.y_rot {args.push(format!("y_rotation={}", y_rot));}
+        for (ty, positive) in &self.types {args.push(format!("type={}{}", pos(*positive), ty));}
+        for (tag, positive) in &self.tags {args.push(format!("tag={}{}", pos(*positive), tag));}
+        for (nbt, positive) in &self.nbt {args.push(format!("nbt={}{}", pos(*positive), nbt));}
+        if !self.advancements.is_empty() {
+            let advancements: Vec<_> = self.advancements.iter().map(|(id, check)| format!("{}={}", id, check)).collect();
+            args.push(format!("advancements={{{}}}", advancements.join(",")));
+        }
+        for (predicate, positive) in &self.predicates {args.push(format!("predicate={}{}", pos(*positive), predicate));}
+        write!(f, "@{}", self.sel)?;
+        if !args.is_empty() {
+            write!(f, "[{}]", args.join(","))?;
         }
-        write!(f, "@{}{}", self.sel, list_start)?;
-        if let Some(limit) = self.limit {write!(f,"limit={}",limit)?;}
-        if let Some(sort) = self.sort {write!(f,"sort={}",sort)?;}
-        if let Some((min, max)) = self.level {write!(f,"level={}..{}",min,max)?;}
-        if let Some((mode,positive)) = self.game_mode {write!(f,"gamemode={}{}",pos(positive),mode)?;}
-        if let Some((name,positive)) = self.name {write!(f,"name={}{}",pos(positive),name)?;}
-        if let Some((min,max)) = self.x_rot {write!(f,"x_rotation={}..{}",min,max)?;}
-        if let Some((min,max)) = self.y_rot {write!(f,"y_rotation={}..{}",min,max)?;}
-        if let Some((ty,positive)) = self.ty {write!(f,"type={}{}",pos(positive),ty)?;}
-        if let Some((tag,positive)) = self.tag {write!(f,"tag={}{}",pos(positive),tag)?;}
-        write!(f, "{}", list_end)?;
         Ok(())
     }
 }
+/// Represents something that can hold a score: either a name (like a fake player `#x`) or a selector.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScoreHolder<'a> {
+    #[doc = "Represents a name, e.g `#x`"] Name(&'a str),
+    #[doc = "Represents a selector, e.g `@s`"] Selector(Box<Selector<'a>>)
+}
+impl<'a> From<&'a str> for ScoreHolder<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::Name(name)
+    }
+}
+impl<'a> From<Selector<'a>> for ScoreHolder<'a> {
+    fn from(selector: Selector<'a>) -> Self {
+        Self::Selector(Box::new(selector))
+    }
+}
+impl Display for ScoreHolder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Selector(selector) => write!(f, "{}", selector)
+        }
+    }
+}
+
+/// A source of nbt data: a block entity, an entity or a storage.  
+/// Syntax: `block <pos>`, `entity <target>` or `storage <id>`
+#[derive(Debug, PartialEq, Clone)]
+pub enum DataTarget<'a> {
+    #[doc = "Represents a block entity, e.g `block ~ ~ ~`"] Block(Coordinates),
+    #[doc = "Represents an entity, e.g `entity @s`"] Entity(Box<Selector<'a>>),
+    #[doc = "Represents a storage, e.g `storage copper:vars`"] Storage(Identifier<'a, 'a>)
+}
+impl Display for DataTarget<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block(location) => write!(f, "block {}", location),
+            Self::Entity(target) => write!(f, "entity {}", target),
+            Self::Storage(id) => write!(f, "storage {}", id)
+        }
+    }
+}
+impl<'a> From<Selector<'a>> for DataTarget<'a> {
+    fn from(target: Selector<'a>) -> Self {
+        Self::Entity(Box::new(target))
+    }
+}
+
 /// Contains methods to create diferent [`Selector`]s.
 pub mod sel {
     use super::{Selector, SelectorType};
@@ -250,6 +507,60 @@ pub mod sel {
     }
 }
 
+/// Represents a range of numbers used in commands, e.g `1..5`, `..5`, `1..` or `3`.
+/// Create it from one of rust's inclusive ranges, or a single number:
+/// ```
+/// # use copper::core::NumberRange;
+/// assert_eq!(NumberRange::from(1..=5).to_string(), "1..5");
+/// assert_eq!(NumberRange::from(..=0.5).to_string(), "..0.5");
+/// assert_eq!(NumberRange::from(3).to_string(), "3");
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NumberRange<N> {
+    /// The minimum value, if any
+    pub min: Option<N>,
+    /// The maximum value, if any
+    pub max: Option<N>
+}
+macro_rules! exact_range {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for NumberRange<$ty> {
+            fn from(n: $ty) -> Self {
+                Self {min: Some(n), max: Some(n)}
+            }
+        }
+    )*};
+}
+exact_range!(i32, i64, u32, u64, f32, f64);
+impl<N: Copy> From<RangeInclusive<N>> for NumberRange<N> {
+    fn from(range: RangeInclusive<N>) -> Self {
+        Self {min: Some(*range.start()), max: Some(*range.end())}
+    }
+}
+impl<N> From<RangeFrom<N>> for NumberRange<N> {
+    fn from(range: RangeFrom<N>) -> Self {
+        Self {min: Some(range.start), max: None}
+    }
+}
+impl<N> From<RangeToInclusive<N>> for NumberRange<N> {
+    fn from(range: RangeToInclusive<N>) -> Self {
+        Self {min: None, max: Some(range.end)}
+    }
+}
+impl<N: Display + PartialEq> Display for NumberRange<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if min == max => write!(f, "{}", min),
+            (min, max) => {
+                if let Some(min) = min {write!(f, "{}", min)?;}
+                f.write_str("..")?;
+                if let Some(max) = max {write!(f, "{}", max)?;}
+                Ok(())
+            }
+        }
+    }
+}
+
 #[inline]
 fn zero(x: f64, f: &mut std::fmt::Formatter<'_>) {if x != 0.0 {write!(f, "{}", x).unwrap();}}
 
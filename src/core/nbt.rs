@@ -0,0 +1,314 @@
+/*!
+Contains the [`Nbt`] enum, which represents nbt data and displays as SNBT,
+and the [`NbtPath`] struct, which points to part of some nbt.
+
+Nbt is usually built using the [`nbt!`](crate::nbt) macro, which creates a compound:
+```
+# use copper::{nbt, core::nbt::Nbt};
+let item = nbt!{
+    Count: 1i8,
+    id: "minecraft:stone",
+    tag: {
+        display: {Name: r#"{"text":"Rock"}"#},
+        Damage: -1,
+        Positions: [[I; 1, 2, 3], [I; -4, 5, 6]]
+    }
+};
+assert_eq!(item.to_string(), r#"{Count:1b,id:"minecraft:stone",tag:{Damage:-1,Positions:[[I;1,2,3],[I;-4,5,6]],display:{Name:'{"text":"Rock"}'}}}"#);
+```
+
+The [`binary`] module reads and writes nbt files, and the [`ser`] module converts serializable types to nbt.
+*/
+
+pub mod binary;
+pub mod ser;
+
+use std::{collections::BTreeMap, fmt::Display, mem::discriminant};
+
+use serde::{Serialize, Serializer};
+
+use super::parse::{is_unquoted, is_unquoted_key};
+
+/// A piece of nbt data. Displays as SNBT, e.g `{Count:1b,id:"minecraft:stone"}`.
+///
+/// SNBT has no way to write NaN, so displaying a float or double which is NaN panics,
+/// as does displaying a list whose elements aren't all the same type, which the game would reject.
+/// Infinities are written as numbers too large for their type, e.g `1e39f`, which the game reads as infinity.
+/// Binary nbt, written by [`binary`] and [`ser`], holds any float.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Nbt {
+    #[doc = "Represents a byte, e.g `1b`. Booleans are stored as bytes."] Byte(i8),
+    #[doc = "Represents a short, e.g `1s`"] Short(i16),
+    #[doc = "Represents an int, e.g `1`"] Int(i32),
+    #[doc = "Represents a long, e.g `1L`"] Long(i64),
+    #[doc = "Represents a float, e.g `1.5f`"] Float(f32),
+    #[doc = "Represents a double, e.g `1.5d`"] Double(f64),
+    #[doc = "Represents a string, e.g `\"foo\"`"] String(String),
+    #[doc = "Represents a list, e.g `[1,2]`. Every element must be of the same type."] List(Vec<Nbt>),
+    #[doc = "Represents a compound, e.g `{foo:1}`"] Compound(BTreeMap<String, Nbt>),
+    #[doc = "Represents a byte array, e.g `[B;1b,2b]`"] ByteArray(Vec<i8>),
+    #[doc = "Represents an int array, e.g `[I;1,2]`"] IntArray(Vec<i32>),
+    #[doc = "Represents a long array, e.g `[L;1L,2L]`"] LongArray(Vec<i64>)
+}
+
+/// Write a string as an SNBT string, quoting it with whichever quote needs less escaping
+pub (crate) fn write_quoted(f: &mut impl std::fmt::Write, s: &str) -> std::fmt::Result {
+    let quote = if s.contains('"') && !s.contains('\'') {'\''} else {'"'};
+    f.write_char(quote)?;
+    for c in s.chars() {
+        if c == quote || c == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+    f.write_char(quote)
+}
+
+pub (crate) fn write_compound(f: &mut std::fmt::Formatter<'_>, compound: &BTreeMap<String, Nbt>) -> std::fmt::Result {
+    f.write_str("{")?;
+    for (i, (key, value)) in compound.iter().enumerate() {
+        if i > 0 {f.write_str(",")?;}
+        if !key.is_empty() && key.chars().all(is_unquoted) {
+            f.write_str(key)?;
+        } else {
+            write_quoted(f, key)?;
+        }
+        write!(f, ":{}", value)?;
+    }
+    f.write_str("}")
+}
+
+fn write_list<T>(f: &mut std::fmt::Formatter<'_>, prefix: &str, values: &[T], mut write: impl FnMut(&mut std::fmt::Formatter<'_>, &T) -> std::fmt::Result) -> std::fmt::Result {
+    write!(f, "[{}", prefix)?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {f.write_str(",")?;}
+        write(f, value)?;
+    }
+    f.write_str("]")
+}
+
+impl Display for Nbt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Byte(n) => write!(f, "{}b", n),
+            Self::Short(n) => write!(f, "{}s", n),
+            Self::Int(n) => write!(f, "{}", n),
+            Self::Long(n) => write!(f, "{}L", n),
+            Self::Float(n) if n.is_nan() => panic!("NaN can't be written as SNBT"),
+            Self::Double(n) if n.is_nan() => panic!("NaN can't be written as SNBT"),
+            Self::Float(n) if n.is_infinite() => write!(f, "{}1e39f", if *n < 0.0 {"-"} else {""}),
+            Self::Double(n) if n.is_infinite() => write!(f, "{}1e309d", if *n < 0.0 {"-"} else {""}),
+            Self::Float(n) => write!(f, "{}f", n),
+            Self::Double(n) => write!(f, "{}d", n),
+            Self::String(s) => write_quoted(f, s),
+            Self::List(values) => {
+                assert!(values.windows(2).all(|pair| discriminant(&pair[0]) == discriminant(&pair[1])), "nbt list elements must all be the same type");
+                write_list(f, "", values, |f, value| write!(f, "{}", value))
+            }
+            Self::Compound(compound) => write_compound(f, compound),
+            Self::ByteArray(values) => write_list(f, "B;", values, |f, n| write!(f, "{}b", n)),
+            Self::IntArray(values) => write_list(f, "I;", values, |f, n| write!(f, "{}", n)),
+            Self::LongArray(values) => write_list(f, "L;", values, |f, n| write!(f, "{}L", n))
+        }
+    }
+}
+/// Nbt is serialized as an SNBT string, which is how it's used in json files like predicates.
+/// [`to_nbt`](ser::to_nbt) keeps it as nbt instead.
+impl Serialize for Nbt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        serializer.serialize_newtype_struct(ser::NBT_NEWTYPE, &ser::Embedded(self))
+    }
+}
+
+macro_rules! nbt_from {
+    ($($ty:ty => $variant:ident),*) => {$(
+        impl From<$ty> for Nbt {
+            fn from(value: $ty) -> Self {
+                Self::$variant(value.into())
+            }
+        }
+    )*};
+}
+nbt_from!(i8 => Byte, i16 => Short, i32 => Int, i64 => Long, f32 => Float, f64 => Double, String => String, &str => String);
+impl From<bool> for Nbt {
+    fn from(value: bool) -> Self {
+        Self::Byte(value as i8)
+    }
+}
+impl<T: Into<Nbt>> From<Vec<T>> for Nbt {
+    fn from(values: Vec<T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+}
+impl From<BTreeMap<String, Nbt>> for Nbt {
+    fn from(compound: BTreeMap<String, Nbt>) -> Self {
+        Self::Compound(compound)
+    }
+}
+
+/// A part of an [`NbtPath`]
+#[derive(Debug, PartialEq, Clone)]
+pub enum NbtPathNode {
+    #[doc = "Represents a child of a compound, e.g `.foo`"] Key(String),
+    #[doc = "Represents matching a compound, at the root or after a key, e.g `foo{bar:1b}`"] Match(BTreeMap<String, Nbt>),
+    #[doc = "Represents an element of a list, e.g `[0]`. Negative indices count from the end."] Index(i32),
+    #[doc = "Represents every element of a list, `[]`"] All,
+    #[doc = "Represents every compound in a list which matches, e.g `[{Slot:0b}]`"] Filter(BTreeMap<String, Nbt>)
+}
+
+/// A path to some nbt, used to read or modify part of a storage, entity or block.
+/// Start it at a key or a compound, then build it with its methods, or parse one with [`NbtPath::parse`]:
+/// ```
+/// # use copper::{nbt, core::nbt::NbtPath};
+/// let path = NbtPath::root("Inventory").filter(nbt!{Slot: 0i8}).key("tag").key("display").key("Name");
+/// assert_eq!(path.to_string(), "Inventory[{Slot:0b}].tag.display.Name");
+/// assert_eq!(NbtPath::root("Pos").index(1).to_string(), "Pos[1]");
+/// assert_eq!(NbtPath::compound(nbt!{OnGround: true}).to_string(), "{OnGround:1b}");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NbtPath {
+    nodes: Vec<NbtPathNode>
+}
+impl NbtPath {
+    /// Create a path starting at a child of the root compound, `key`
+    pub fn root(key: impl Into<String>) -> Self {
+        Self {nodes: vec![NbtPathNode::Key(key.into())]}
+    }
+    /// Create a path which only matches if the root compound matches `compound`, `{...}`.
+    /// Panics if `compound` isn't a compound.
+    pub fn compound(compound: Nbt) -> Self {
+        Self {nodes: vec![NbtPathNode::Match(Self::into_compound(compound))]}
+    }
+    /// The nodes of the path, in order. The first is always a key or a compound.
+    pub fn nodes(&self) -> &[NbtPathNode] {
+        &self.nodes
+    }
+    /// Adds a child of a compound, `.key`
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.nodes.push(NbtPathNode::Key(key.into()));
+        self
+    }
+    /// Only matches if the compound so far matches `compound`, i.e `{...}`.
+    /// Panics if `compound` isn't a compound, or if this isn't directly after a key.
+    pub fn matching(mut self, compound: Nbt) -> Self {
+        assert!(matches!(self.nodes.last(), Some(NbtPathNode::Key(_))), "compounds can only be matched at the root or after a key");
+        self.nodes.push(NbtPathNode::Match(Self::into_compound(compound)));
+        self
+    }
+    /// Adds an element of a list, `[index]`
+    pub fn index(mut self, index: i32) -> Self {
+        self.nodes.push(NbtPathNode::Index(index));
+        self
+    }
+    /// Adds every element of a list, `[]`
+    pub fn all(mut self) -> Self {
+        self.nodes.push(NbtPathNode::All);
+        self
+    }
+    /// Adds every compound in a list which matches `compound`, `[{...}]`. Panics if `compound` isn't a compound.
+    pub fn filter(mut self, compound: Nbt) -> Self {
+        self.nodes.push(NbtPathNode::Filter(Self::into_compound(compound)));
+        self
+    }
+    fn into_compound(compound: Nbt) -> BTreeMap<String, Nbt> {
+        match compound {
+            Nbt::Compound(compound) => compound,
+            other => panic!("expected an nbt compound, found `{}`", other)
+        }
+    }
+}
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                NbtPathNode::Key(key) => {
+                    if i > 0 {f.write_str(".")?;}
+                    if !key.is_empty() && key.chars().all(is_unquoted_key) {
+                        f.write_str(key)?;
+                    } else {
+                        write_quoted(f, key)?;
+                    }
+                }
+                NbtPathNode::Match(compound) => write_compound(f, compound)?,
+                NbtPathNode::Index(index) => write!(f, "[{}]", index)?,
+                NbtPathNode::All => f.write_str("[]")?,
+                NbtPathNode::Filter(compound) => {
+                    f.write_str("[")?;
+                    write_compound(f, compound)?;
+                    f.write_str("]")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+/// Paths are serialized as strings, which is how they're used in json files like item modifiers
+impl Serialize for NbtPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+/// Create an nbt compound ([`Nbt::Compound`]) using syntax similar to SNBT.
+///
+/// Keys are identifiers or string literals. Values can be:
+/// - a compound, `{...}`
+/// - a list, `[...]`
+/// - an array, `[B; ...]`, `[I; ...]` or `[L; ...]`, containing expressions
+/// - a single token or negated token converted with `Nbt::from`, e.g `1i8`, `-2.5`, `"foo"` or `(x + 1)`
+///
+/// Number types come from Rust, so `1i8` is a byte and unsuffixed integers are ints. Keys are sorted, so the output is stable.
+/// ```
+/// # use copper::{nbt, core::nbt::Nbt};
+/// let name = "Steve";
+/// assert_eq!(nbt!{"custom name": name, Health: 20.0f32, Tags: ["a", "b"]}.to_string(), r#"{Health:20f,Tags:["a","b"],"custom name":"Steve"}"#);
+/// assert_eq!(nbt!{}, Nbt::Compound(Default::default()));
+/// ```
+#[macro_export]
+macro_rules! nbt {
+    (@value {$($body:tt)*}) => {$crate::nbt!{$($body)*}};
+    (@value [B; $($value:expr),* $(,)?]) => {$crate::core::nbt::Nbt::ByteArray(vec![$($value),*])};
+    (@value [I; $($value:expr),* $(,)?]) => {$crate::core::nbt::Nbt::IntArray(vec![$($value),*])};
+    (@value [L; $($value:expr),* $(,)?]) => {$crate::core::nbt::Nbt::LongArray(vec![$($value),*])};
+    (@value [$($body:tt)*]) => {{
+        #[allow(unused_mut)]
+        let mut list = Vec::new();
+        $crate::nbt!(@list list $($body)*);
+        $crate::core::nbt::Nbt::List(list)
+    }};
+    (@value $value:expr) => {$crate::core::nbt::Nbt::from($value)};
+
+    (@list $list:ident) => {};
+    (@list $list:ident - $value:tt $(, $($rest:tt)*)?) => {
+        $list.push($crate::nbt!(@value -$value));
+        $crate::nbt!(@list $list $($($rest)*)?);
+    };
+    (@list $list:ident $value:tt $(, $($rest:tt)*)?) => {
+        $list.push($crate::nbt!(@value $value));
+        $crate::nbt!(@list $list $($($rest)*)?);
+    };
+
+    (@key $key:ident) => {String::from(stringify!($key))};
+    (@key $key:literal) => {String::from($key)};
+
+    (@compound $compound:ident) => {};
+    (@compound $compound:ident $key:tt : - $value:tt $(, $($rest:tt)*)?) => {
+        $compound.insert($crate::nbt!(@key $key), $crate::nbt!(@value -$value));
+        $crate::nbt!(@compound $compound $($($rest)*)?);
+    };
+    (@compound $compound:ident $key:tt : $value:tt $(, $($rest:tt)*)?) => {
+        $compound.insert($crate::nbt!(@key $key), $crate::nbt!(@value $value));
+        $crate::nbt!(@compound $compound $($($rest)*)?);
+    };
+
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut compound = std::collections::BTreeMap::new();
+        $crate::nbt!(@compound compound $($body)*);
+        $crate::core::nbt::Nbt::Compound(compound)
+    }};
+}
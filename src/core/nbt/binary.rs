@@ -0,0 +1,260 @@
+/*!
+Reads and writes nbt in minecraft's binary format, used by `.nbt` files like structure templates.
+
+A file holds a single named tag, usually a compound with an empty name, and is usually compressed with gzip:
+```
+# use copper::{nbt, core::nbt::binary::{self, Compression}};
+let nbt = nbt!{DataVersion: 2586, size: [1, 1, 1]};
+let mut bytes = Vec::new();
+binary::write(&mut bytes, "", &nbt, Compression::Gzip).unwrap();
+assert_eq!(binary::read(&bytes[..]).unwrap(), (String::new(), nbt));
+```
+*/
+
+use std::{collections::BTreeMap, convert::{TryFrom, TryInto}, io::{self, Read, Write}};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+
+use super::Nbt;
+
+/// How a binary nbt file is compressed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Compression {
+    #[doc = "Represents an uncompressed file"] None,
+    #[doc = "Represents a gzip compressed file, which is used for structures"] Gzip
+}
+
+/// How deeply lists and compounds can be nested when reading, which matches minecraft's limit
+const MAX_DEPTH: usize = 512;
+
+impl Nbt {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Byte(_) => 1,
+            Self::Short(_) => 2,
+            Self::Int(_) => 3,
+            Self::Long(_) => 4,
+            Self::Float(_) => 5,
+            Self::Double(_) => 6,
+            Self::ByteArray(_) => 7,
+            Self::String(_) => 8,
+            Self::List(_) => 9,
+            Self::Compound(_) => 10,
+            Self::IntArray(_) => 11,
+            Self::LongArray(_) => 12
+        }
+    }
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn write_length(out: &mut impl Write, length: usize) -> io::Result<()> {
+    let length = i32::try_from(length).map_err(|_| invalid("list or array is too long"))?;
+    out.write_all(&length.to_be_bytes())
+}
+
+/// Write a string in java's modified utf-8, where nul is two bytes and other characters outside the BMP are surrogate pairs
+fn write_string(out: &mut impl Write, s: &str) -> io::Result<()> {
+    let mut bytes = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        match unit {
+            1..=0x7f => bytes.push(unit as u8),
+            0 | 0x80..=0x7ff => bytes.extend([0xc0 | (unit >> 6) as u8, 0x80 | (unit & 0x3f) as u8]),
+            _ => bytes.extend([0xe0 | (unit >> 12) as u8, 0x80 | ((unit >> 6) & 0x3f) as u8, 0x80 | (unit & 0x3f) as u8])
+        }
+    }
+    let length = u16::try_from(bytes.len()).map_err(|_| invalid("string is too long"))?;
+    out.write_all(&length.to_be_bytes())?;
+    out.write_all(&bytes)
+}
+
+fn write_payload(out: &mut impl Write, nbt: &Nbt) -> io::Result<()> {
+    match nbt {
+        Nbt::Byte(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::Short(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::Int(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::Long(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::Float(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::Double(n) => out.write_all(&n.to_be_bytes()),
+        Nbt::String(s) => write_string(out, s),
+        Nbt::List(values) => {
+            let id = values.first().map_or(0, Nbt::id);
+            if values.iter().any(|value| value.id() != id) {
+                return Err(invalid("list elements must all be the same type"));
+            }
+            out.write_all(&[id])?;
+            write_length(out, values.len())?;
+            values.iter().try_for_each(|value| write_payload(out, value))
+        }
+        Nbt::Compound(compound) => {
+            for (key, value) in compound {
+                write_named(out, key, value)?;
+            }
+            out.write_all(&[0])
+        }
+        Nbt::ByteArray(values) => {
+            write_length(out, values.len())?;
+            values.iter().try_for_each(|n| out.write_all(&n.to_be_bytes()))
+        }
+        Nbt::IntArray(values) => {
+            write_length(out, values.len())?;
+            values.iter().try_for_each(|n| out.write_all(&n.to_be_bytes()))
+        }
+        Nbt::LongArray(values) => {
+            write_length(out, values.len())?;
+            values.iter().try_for_each(|n| out.write_all(&n.to_be_bytes()))
+        }
+    }
+}
+
+fn write_named(out: &mut impl Write, name: &str, nbt: &Nbt) -> io::Result<()> {
+    out.write_all(&[nbt.id()])?;
+    write_string(out, name)?;
+    write_payload(out, nbt)
+}
+
+/// Write `nbt` as a binary nbt file, with the root tag called `name`.
+/// Fails if a list's elements aren't all the same type, or a string is longer than 65535 bytes.
+pub fn write(out: impl Write, name: &str, nbt: &Nbt, compression: Compression) -> io::Result<()> {
+    match compression {
+        Compression::None => {
+            let mut out = io::BufWriter::new(out);
+            write_named(&mut out, name, nbt)?;
+            out.flush()
+        }
+        Compression::Gzip => {
+            let mut out = GzEncoder::new(io::BufWriter::new(out), flate2::Compression::default());
+            write_named(&mut out, name, nbt)?;
+            out.finish()?.flush()
+        }
+    }
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    depth: usize
+}
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        if self.input.len() < N {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let (bytes, rest) = self.input.split_at(N);
+        self.input = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+    fn length(&mut self) -> io::Result<usize> {
+        let length = i32::from_be_bytes(self.bytes()?);
+        usize::try_from(length).map_err(|_| invalid("negative length"))
+    }
+    /// Read `length` values, without trusting `length` for the allocation
+    fn values<T>(&mut self, length: usize, mut read: impl FnMut(&mut Self) -> io::Result<T>) -> io::Result<Vec<T>> {
+        let mut values = Vec::with_capacity(length.min(self.input.len()));
+        for _ in 0..length {
+            values.push(read(self)?);
+        }
+        Ok(values)
+    }
+    fn string(&mut self) -> io::Result<String> {
+        let length = u16::from_be_bytes(self.bytes()?) as usize;
+        if self.input.len() < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let (bytes, rest) = self.input.split_at(length);
+        self.input = rest;
+        let mut units = Vec::with_capacity(length);
+        let mut i = 0;
+        while i < bytes.len() {
+            let continuation = |j: usize| bytes.get(i + j).copied().filter(|b| b & 0xc0 == 0x80).map(|b| (b & 0x3f) as u16);
+            let (unit, size) = match bytes[i] {
+                b @ 0x01..=0x7f => (b as u16, 1),
+                b @ 0xc0..=0xdf => (((b & 0x1f) as u16) << 6 | continuation(1).ok_or_else(|| invalid("invalid modified utf-8"))?, 2),
+                b @ 0xe0..=0xef => {
+                    let (second, third) = continuation(1).zip(continuation(2)).ok_or_else(|| invalid("invalid modified utf-8"))?;
+                    (((b & 0x0f) as u16) << 12 | second << 6 | third, 3)
+                }
+                _ => return Err(invalid("invalid modified utf-8"))
+            };
+            units.push(unit);
+            i += size;
+        }
+        String::from_utf16(&units).map_err(|_| invalid("invalid modified utf-8"))
+    }
+    fn payload(&mut self, id: u8) -> io::Result<Nbt> {
+        Ok(match id {
+            1 => Nbt::Byte(i8::from_be_bytes(self.bytes()?)),
+            2 => Nbt::Short(i16::from_be_bytes(self.bytes()?)),
+            3 => Nbt::Int(i32::from_be_bytes(self.bytes()?)),
+            4 => Nbt::Long(i64::from_be_bytes(self.bytes()?)),
+            5 => Nbt::Float(f32::from_be_bytes(self.bytes()?)),
+            6 => Nbt::Double(f64::from_be_bytes(self.bytes()?)),
+            7 => {
+                let length = self.length()?;
+                Nbt::ByteArray(self.values(length, |reader| Ok(i8::from_be_bytes(reader.bytes()?)))?)
+            }
+            8 => Nbt::String(self.string()?),
+            9 | 10 => {
+                self.depth += 1;
+                if self.depth > MAX_DEPTH {
+                    return Err(invalid("nbt is nested too deeply"));
+                }
+                let nbt = if id == 9 {
+                    let [element] = self.bytes()?;
+                    let length = self.length()?;
+                    if element == 0 && length > 0 {
+                        return Err(invalid("non-empty list of end tags"));
+                    }
+                    Nbt::List(self.values(length, |reader| reader.payload(element))?)
+                } else {
+                    let mut compound = BTreeMap::new();
+                    loop {
+                        match self.bytes()? {
+                            [0] => break,
+                            [id] => {
+                                let name = self.string()?;
+                                compound.insert(name, self.payload(id)?);
+                            }
+                        }
+                    }
+                    Nbt::Compound(compound)
+                };
+                self.depth -= 1;
+                nbt
+            }
+            11 => {
+                let length = self.length()?;
+                Nbt::IntArray(self.values(length, |reader| Ok(i32::from_be_bytes(reader.bytes()?)))?)
+            }
+            12 => {
+                let length = self.length()?;
+                Nbt::LongArray(self.values(length, |reader| Ok(i64::from_be_bytes(reader.bytes()?)))?)
+            }
+            id => return Err(invalid(format!("invalid tag id {}", id)))
+        })
+    }
+}
+
+/// Read a binary nbt file, returning the name of the root tag and its value.
+/// Gzip compression is detected automatically.
+pub fn read(mut input: impl Read) -> io::Result<(String, Nbt)> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(&bytes[..]).read_to_end(&mut decompressed)?;
+        bytes = decompressed;
+    }
+    let mut reader = Reader {input: &bytes, depth: 0};
+    let [id] = reader.bytes()?;
+    if id == 0 {
+        return Err(invalid("the root tag can't be an end tag"));
+    }
+    let name = reader.string()?;
+    let nbt = reader.payload(id)?;
+    if !reader.input.is_empty() {
+        return Err(invalid("unexpected bytes after the root tag"));
+    }
+    Ok((name, nbt))
+}
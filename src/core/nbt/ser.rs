@@ -0,0 +1,327 @@
+/*!
+A serde [`Serializer`] which turns any [`Serialize`] type into [`Nbt`], so it can be written with [`binary::write`](super::binary::write).
+
+Numbers keep their types, with unsigned integers widened to the next signed type (so `u8` becomes a short).
+Structs and maps become compounds, fields which are `None` are left out, sequences and tuples become lists,
+and enums are externally tagged, like in `serde_json`. An [`Nbt`] inside the value is kept as it is.
+```
+# use copper::{nbt, core::nbt::ser::to_nbt};
+#[derive(serde::Serialize)]
+struct Block {
+    pos: (i32, i32, i32),
+    state: i32,
+    nbt: Option<copper::core::nbt::Nbt>
+}
+let block = Block {pos: (0, 1, 0), state: 0, nbt: Some(nbt!{id: "minecraft:chest"})};
+assert_eq!(to_nbt(&block).unwrap(), nbt!{pos: [0, 1, 0], state: 0, nbt: {id: "minecraft:chest"}});
+```
+*/
+
+use std::{collections::BTreeMap, convert::TryFrom, fmt::Display, mem::discriminant};
+
+use serde::{Serialize, ser::{self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant}};
+
+use super::Nbt;
+
+/// The name of the newtype struct [`Nbt`] serializes as, so this serializer can tell it apart from a string
+pub (super) const NBT_NEWTYPE: &str = "$copper::Nbt";
+/// The names of the newtype structs int and long arrays serialize as inside an embedded [`Nbt`], to tell them apart from lists
+const INT_ARRAY_NEWTYPE: &str = "$copper::IntArray";
+const LONG_ARRAY_NEWTYPE: &str = "$copper::LongArray";
+
+/// The value inside [`Nbt`]'s newtype struct. It's written as SNBT by human readable serializers like `serde_json`,
+/// but this serializer isn't human readable inside the newtype, so it gets the nbt's structure and keeps every value as it is.
+pub (super) struct Embedded<'a>(pub &'a Nbt);
+impl Serialize for Embedded<'_> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self.0)
+        } else {
+            Structure(self.0).serialize(serializer)
+        }
+    }
+}
+
+/// Serializes nbt as the serde types this serializer turns back into the same nbt
+struct Structure<'a>(&'a Nbt);
+impl Serialize for Structure<'_> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self.0 {
+            Nbt::Byte(v) => serializer.serialize_i8(*v),
+            Nbt::Short(v) => serializer.serialize_i16(*v),
+            Nbt::Int(v) => serializer.serialize_i32(*v),
+            Nbt::Long(v) => serializer.serialize_i64(*v),
+            Nbt::Float(v) => serializer.serialize_f32(*v),
+            Nbt::Double(v) => serializer.serialize_f64(*v),
+            Nbt::String(v) => serializer.serialize_str(v),
+            Nbt::List(values) => serializer.collect_seq(values.iter().map(Structure)),
+            Nbt::Compound(compound) => serializer.collect_map(compound.iter().map(|(key, value)| (key, Structure(value)))),
+            Nbt::ByteArray(values) => serializer.serialize_bytes(&values.iter().map(|&b| b as u8).collect::<Vec<_>>()),
+            Nbt::IntArray(values) => serializer.serialize_newtype_struct(INT_ARRAY_NEWTYPE, values),
+            Nbt::LongArray(values) => serializer.serialize_newtype_struct(LONG_ARRAY_NEWTYPE, values)
+        }
+    }
+}
+
+/// The error returned by [`to_nbt`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Error(String);
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl std::error::Error for Error {}
+impl ser::Error for Error {
+    fn custom<T: Display>(message: T) -> Self {
+        Self(message.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+fn error<T>(message: &str) -> Result<T> {
+    Err(Error(message.to_string()))
+}
+
+/// Convert a value to nbt. Fails if the value is `None` or `()`, if a sequence's elements aren't all the same type,
+/// if a map key isn't a string or an integer, or if a `u64` is too big for a long.
+pub fn to_nbt<T: Serialize + ?Sized>(value: &T) -> Result<Nbt> {
+    value.serialize(Serializer::default())?.map_or_else(|| error("can't convert a missing value to nbt"), Ok)
+}
+
+/// Serializes a value into `Some(nbt)`, or `None` if it's missing and should be left out of a compound.
+/// It's only not human readable for the value inside [`Nbt`]'s newtype struct, see [`Embedded`].
+#[derive(Default)]
+struct Serializer {
+    embedded: bool
+}
+
+fn list(values: Vec<Nbt>) -> Result<Nbt> {
+    match values.first() {
+        Some(first) if values.iter().any(|value| discriminant(value) != discriminant(first)) =>
+            error("list elements must all be the same type"),
+        _ => Ok(Nbt::List(values))
+    }
+}
+
+fn tagged(variant: &str, value: Nbt) -> Option<Nbt> {
+    Some(Nbt::Compound(BTreeMap::from([(variant.to_string(), value)])))
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = CompoundSerializer;
+    type SerializeStruct = CompoundSerializer;
+    type SerializeStructVariant = CompoundSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {Ok(Some(Nbt::from(v)))}
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {Ok(Some(Nbt::Byte(v)))}
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {Ok(Some(Nbt::Short(v)))}
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {Ok(Some(Nbt::Int(v)))}
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {Ok(Some(Nbt::Long(v)))}
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {Ok(Some(Nbt::Short(v.into())))}
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {Ok(Some(Nbt::Int(v.into())))}
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {Ok(Some(Nbt::Long(v.into())))}
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        i64::try_from(v).map(|v| Some(Nbt::Long(v))).or_else(|_| error("u64 is too big for a long"))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {Ok(Some(Nbt::Float(v)))}
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {Ok(Some(Nbt::Double(v)))}
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {Ok(Some(Nbt::String(v.to_string())))}
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {Ok(Some(Nbt::from(v)))}
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        Ok(Some(Nbt::ByteArray(v.iter().map(|&b| b as i8).collect())))
+    }
+    fn serialize_none(self) -> Result<Self::Ok> {Ok(None)}
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok> {value.serialize(self)}
+    fn serialize_unit(self) -> Result<Self::Ok> {Ok(None)}
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {Ok(Some(Nbt::Compound(BTreeMap::new())))}
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Self::Ok> {
+        Ok(Some(Nbt::from(variant)))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok> {
+        let value = match name {
+            NBT_NEWTYPE => return value.serialize(Serializer {embedded: true}),
+            INT_ARRAY_NEWTYPE | LONG_ARRAY_NEWTYPE => value.serialize(Serializer::default())?,
+            _ => return value.serialize(self)
+        };
+        Ok(value.map(|value| match value {
+            Nbt::List(values) if name == INT_ARRAY_NEWTYPE =>
+                Nbt::IntArray(values.into_iter().filter_map(|v| if let Nbt::Int(v) = v {Some(v)} else {None}).collect()),
+            Nbt::List(values) => Nbt::LongArray(values.into_iter().filter_map(|v| if let Nbt::Long(v) = v {Some(v)} else {None}).collect()),
+            value => value
+        }))
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<Self::Ok> {
+        Ok(tagged(variant, to_nbt(value)?))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SeqSerializer {values: Vec::with_capacity(len.unwrap_or(0)), variant: None})
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {self.serialize_seq(Some(len))}
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct> {self.serialize_seq(Some(len))}
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> Result<Self::SerializeTupleVariant> {
+        Ok(SeqSerializer {values: Vec::with_capacity(len), variant: Some(variant)})
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(CompoundSerializer {compound: BTreeMap::new(), key: None, variant: None})
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {self.serialize_map(None)}
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant> {
+        Ok(CompoundSerializer {compound: BTreeMap::new(), key: None, variant: Some(variant)})
+    }
+    fn is_human_readable(&self) -> bool {!self.embedded}
+}
+
+/// Collects the elements of a list, which is wrapped in a compound for tuple variants
+struct SeqSerializer {
+    values: Vec<Nbt>,
+    variant: Option<&'static str>
+}
+impl SeqSerializer {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match value.serialize(Serializer::default())? {
+            Some(value) => {self.values.push(value); Ok(())}
+            None => error("lists can't contain missing values")
+        }
+    }
+    fn finish(self) -> Result<Option<Nbt>> {
+        let values = list(self.values)?;
+        Ok(match self.variant {
+            Some(name) => tagged(name, values),
+            None => Some(values)
+        })
+    }
+}
+impl SerializeSeq for SeqSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {self.element(value)}
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+impl SerializeTuple for SeqSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {self.element(value)}
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {self.element(value)}
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+impl SerializeTupleVariant for SeqSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {self.element(value)}
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+
+/// Collects the entries of a compound, which is wrapped in another compound for struct variants
+struct CompoundSerializer {
+    compound: BTreeMap<String, Nbt>,
+    key: Option<String>,
+    variant: Option<&'static str>
+}
+impl CompoundSerializer {
+    fn entry<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<()> {
+        if let Some(value) = value.serialize(Serializer::default())? {
+            self.compound.insert(key, value);
+        }
+        Ok(())
+    }
+    fn finish(self) -> Result<Option<Nbt>> {
+        let compound = Nbt::Compound(self.compound);
+        Ok(match self.variant {
+            Some(name) => tagged(name, compound),
+            None => Some(compound)
+        })
+    }
+}
+impl SerializeMap for CompoundSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().expect("serialize_value called before serialize_key");
+        self.entry(key, value)
+    }
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+impl SerializeStruct for CompoundSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.entry(key.to_string(), value)
+    }
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+impl SerializeStructVariant for CompoundSerializer {
+    type Ok = Option<Nbt>;
+    type Error = Error;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.entry(key.to_string(), value)
+    }
+    fn end(self) -> Result<Self::Ok> {self.finish()}
+}
+
+/// Serializes map keys, which must be strings or integers
+struct KeySerializer;
+
+macro_rules! key_to_string {
+    ($($method:ident $ty:ty),*) => {$(
+        fn $method(self, v: $ty) -> Result<String> {Ok(v.to_string())}
+    )*};
+}
+macro_rules! key_error {
+    ($($method:ident $ty:ty),*) => {$(
+        fn $method(self, _v: $ty) -> Result<String> {error("compound keys must be strings or integers")}
+    )*};
+}
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    key_to_string!(serialize_i8 i8, serialize_i16 i16, serialize_i32 i32, serialize_i64 i64,
+        serialize_u8 u8, serialize_u16 u16, serialize_u32 u32, serialize_u64 u64, serialize_char char, serialize_str &str);
+    key_error!(serialize_bool bool, serialize_f32 f32, serialize_f64 f64, serialize_bytes &[u8]);
+    fn serialize_none(self) -> Result<String> {error("compound keys must be strings or integers")}
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {value.serialize(self)}
+    fn serialize_unit(self) -> Result<String> {error("compound keys must be strings or integers")}
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String> {error("compound keys must be strings or integers")}
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<String> {Ok(variant.to_string())}
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String> {value.serialize(self)}
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<String> {
+        error("compound keys must be strings or integers")
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {error("compound keys must be strings or integers")}
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {error("compound keys must be strings or integers")}
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct> {
+        error("compound keys must be strings or integers")
+    }
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant> {
+        error("compound keys must be strings or integers")
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {error("compound keys must be strings or integers")}
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {error("compound keys must be strings or integers")}
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant> {
+        error("compound keys must be strings or integers")
+    }
+}
@@ -0,0 +1,529 @@
+use std::{borrow::Cow, collections::BTreeMap, convert::TryFrom, fmt::Display, mem::discriminant, str::FromStr};
+
+use super::{GameMode, Identifier, NumberRange, Selector, SelectorAdvancement, SelectorSort, SelectorType, nbt::{Nbt, NbtPath, NbtPathNode}};
+
+/// The error returned by [`Selector::parse`], [`Nbt::parse`] and [`NbtPath::parse`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    /// What went wrong
+    pub message: String,
+    /// The byte offset in the input where it went wrong
+    pub position: usize
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// Whether a character can be used in an unquoted string, like a tag or objective
+pub(super) fn is_unquoted(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+type Result<T> = std::result::Result<T, ParseError>;
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize
+}
+impl<'a> Reader<'a> {
+    fn error<T>(&self, message: impl Display) -> Result<T> {
+        Err(ParseError {message: message.to_string(), position: self.pos})
+    }
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+    fn skip_whitespace(&mut self) {
+        while self.peek() == Some(' ') {
+            self.pos += 1;
+        }
+    }
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.peek() {
+            Some(c) if c == expected => {self.pos += 1; Ok(())}
+            Some(c) => self.error(format!("expected `{}`, found `{}`", expected, c)),
+            None => self.error(format!("expected `{}`, found the end of the selector", expected))
+        }
+    }
+    /// Reads a `!` if there is one, returning the positiveness
+    fn positive(&mut self) -> bool {
+        if self.peek() == Some('!') {
+            self.pos += 1;
+            false
+        } else {true}
+    }
+    fn read_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !pred(c) {break;}
+            self.pos += c.len_utf8();
+        }
+        &self.input[start..self.pos]
+    }
+    fn unquoted(&mut self) -> &'a str {
+        self.read_while(is_unquoted)
+    }
+    /// Reads an unquoted string, or a quoted one which may escape its quote and backslashes
+    fn string(&mut self) -> Result<Cow<'a, str>> {
+        match self.peek() {
+            Some('"' | '\'') => self.nbt_string(is_unquoted).map(Cow::Owned),
+            _ => Ok(Cow::Borrowed(self.unquoted()))
+        }
+    }
+    fn parsed<T: FromStr>(&mut self, what: &str, pred: impl Fn(char) -> bool) -> Result<T> {
+        let start = self.pos;
+        let s = self.read_while(pred);
+        s.parse().or_else(|_| {self.pos = start; self.error(format!("invalid {} `{}`", what, s))})
+    }
+    fn number<T: FromStr>(&mut self) -> Result<T> {
+        self.parsed("number", |c| c.is_ascii_digit() || matches!(c, '-' | '.'))
+    }
+    fn range<T: FromStr + Copy>(&mut self) -> Result<NumberRange<T>> {
+        let start = self.pos;
+        let s = self.read_while(|c| c.is_ascii_digit() || matches!(c, '-' | '.'));
+        let parse = |s: &str| if s.is_empty() {Ok(None)} else {s.parse().map(Some)};
+        let range = match s.split_once("..") {
+            Some((min, max)) => parse(min).and_then(|min| Ok(NumberRange {min, max: parse(max)?})),
+            None => s.parse().map(|n| NumberRange {min: Some(n), max: Some(n)})
+        };
+        match range {
+            Ok(NumberRange {min: None, max: None}) | Err(_) => {self.pos = start; self.error(format!("invalid range `{}`", s))}
+            Ok(range) => Ok(range)
+        }
+    }
+    fn identifier(&mut self) -> Result<Identifier<'a, 'a>> {
+        let start = self.pos;
+        let s = self.read_while(|c| is_unquoted(c) || matches!(c, ':' | '/'));
+        Identifier::parse(s).map_or_else(|| {self.pos = start; self.error(format!("invalid identifier `{}`", s))}, Ok)
+    }
+    fn bool(&mut self) -> Result<bool> {
+        self.parsed("boolean", |c| c.is_ascii_alphabetic())
+    }
+    /// Reads a `{key=value,...}` map, calling `entry` after each key and `=`
+    fn map(&mut self, mut entry: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        self.expect('{')?;
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            entry(self)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {self.pos += 1; self.skip_whitespace()}
+                Some('}') => {self.pos += 1; return Ok(())}
+                _ => return self.error("expected `,` or `}`")
+            }
+        }
+    }
+    fn key(&mut self) -> Result<&'a str> {
+        let key = self.unquoted();
+        self.skip_whitespace();
+        self.expect('=')?;
+        self.skip_whitespace();
+        Ok(key)
+    }
+    fn argument(&mut self, selector: &mut Selector<'a>) -> Result<()> {
+        let start = self.pos;
+        let key = self.key()?;
+        macro_rules! set {
+            ($field:ident = $value:expr) => {{
+                if selector.$field.is_some() {
+                    self.pos = start;
+                    return self.error(format!("duplicate argument `{}`", key));
+                }
+                selector.$field = Some($value);
+            }};
+        }
+        match key {
+            "x" => set!(x = self.number()?),
+            "y" => set!(y = self.number()?),
+            "z" => set!(z = self.number()?),
+            "distance" => set!(distance = self.range()?),
+            "dx" => set!(dx = self.number()?),
+            "dy" => set!(dy = self.number()?),
+            "dz" => set!(dz = self.number()?),
+            "scores" => self.map(|reader| {
+                let objective = reader.key()?;
+                selector.scores.push((objective, reader.range()?));
+                Ok(())
+            })?,
+            "limit" => set!(limit = self.number()?),
+            "sort" => set!(sort = self.parsed("sort", |c| c.is_ascii_alphabetic())?),
+            "level" => set!(level = self.range()?),
+            "gamemode" => {
+                let positive = self.positive();
+                set!(game_mode = (self.parsed("game mode", |c| c.is_ascii_alphabetic())?, positive))
+            }
+            "team" => {
+                let positive = self.positive();
+                set!(team = (self.unquoted(), positive))
+            }
+            "name" => {
+                let positive = self.positive();
+                selector.names.push((self.string()?, positive));
+            }
+            "x_rotation" => set!(x_rot = self.range()?),
+            "y_rotation" => set!(y_rot = self.range()?),
+            "type" => {
+                let positive = self.positive();
+                let ty = self.parsed("entity type", |c| is_unquoted(c) || matches!(c, ':' | '/'))?;
+                selector.types.push((ty, positive));
+            }
+            "tag" => {
+                let positive = self.positive();
+                selector.tags.push((self.unquoted(), positive));
+            }
+            "nbt" => {
+                let positive = self.positive();
+                selector.nbt.push((self.compound_nbt().map(Nbt::Compound)?, positive));
+            }
+            "advancements" => self.map(|reader| {
+                let id = reader.identifier()?;
+                reader.skip_whitespace();
+                reader.expect('=')?;
+                reader.skip_whitespace();
+                let check = if reader.peek() == Some('{') {
+                    let mut criteria = Vec::new();
+                    reader.map(|reader| {
+                        let criterion = reader.key()?;
+                        criteria.push((criterion, reader.bool()?));
+                        Ok(())
+                    })?;
+                    SelectorAdvancement::Criteria(criteria)
+                } else {
+                    SelectorAdvancement::Complete(reader.bool()?)
+                };
+                selector.advancements.push((id, check));
+                Ok(())
+            })?,
+            "predicate" => {
+                let positive = self.positive();
+                selector.predicates.push((self.identifier()?, positive));
+            }
+            _ => {
+                self.pos = start;
+                return self.error(format!("unknown argument `{}`", key));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a character can be used in an unquoted nbt path key
+pub(super) fn is_unquoted_key(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '"' | '\'' | '[' | ']' | '{' | '}' | '.')
+}
+
+impl Reader<'_> {
+    /// Reads a quoted string with `\\` and `\"` escapes, or an unquoted one
+    fn nbt_string(&mut self, unquoted: impl Fn(char) -> bool) -> Result<String> {
+        let quote = match self.peek() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Ok(self.read_while(unquoted).to_string())
+        };
+        let start = self.pos;
+        self.pos += 1;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some(c) if c == quote || c == '\\' => string.push(c),
+                    _ => {self.pos -= 1; return self.error("invalid escape sequence")}
+                },
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+                None => {self.pos = start; return self.error("unclosed quoted string")}
+            }
+        }
+    }
+    fn compound_nbt(&mut self) -> Result<BTreeMap<String, Nbt>> {
+        self.expect('{')?;
+        let mut compound = BTreeMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(compound);
+        }
+        loop {
+            let start = self.pos;
+            let key = self.nbt_string(is_unquoted)?;
+            if key.is_empty() && self.pos == start {
+                return self.error("expected a key");
+            }
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            let value = self.nbt()?;
+            if compound.insert(key, value).is_some() {
+                self.pos = start;
+                return self.error("duplicate key");
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {self.pos += 1; self.skip_whitespace()}
+                Some('}') => {self.pos += 1; return Ok(compound)}
+                _ => return self.error("expected `,` or `}`")
+            }
+        }
+    }
+    /// Reads the values of a list or array, after the `[` and any prefix
+    fn list_values(&mut self) -> Result<Vec<Nbt>> {
+        let mut values: Vec<Nbt> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(values);
+        }
+        loop {
+            let start = self.pos;
+            let value = self.nbt()?;
+            if matches!(values.first(), Some(first) if discriminant(first) != discriminant(&value)) {
+                self.pos = start;
+                return self.error("list elements must all be the same type");
+            }
+            values.push(value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {self.pos += 1; self.skip_whitespace()}
+                Some(']') => {self.pos += 1; return Ok(values)}
+                _ => return self.error("expected `,` or `]`")
+            }
+        }
+    }
+    fn list_nbt(&mut self) -> Result<Nbt> {
+        self.expect('[')?;
+        let rest = &self.input[self.pos..];
+        let array = ["B;", "I;", "L;"].iter().position(|prefix| rest.starts_with(prefix));
+        let Some(array) = array else {
+            return Ok(Nbt::List(self.list_values()?));
+        };
+        self.pos += 2;
+        let start = self.pos;
+        let values = self.list_values()?;
+        let invalid = |reader: &mut Self| {reader.pos = start; reader.error(format!("invalid element in {} array", ["byte", "int", "long"][array]))};
+        macro_rules! array {
+            ($variant:ident, $element:ident) => {
+                match values.into_iter().map(|value| if let Nbt::$element(n) = value {Some(n)} else {None}).collect() {
+                    Some(values) => Nbt::$variant(values),
+                    None => return invalid(self)
+                }
+            };
+        }
+        Ok(match array {
+            0 => array!(ByteArray, Byte),
+            1 => array!(IntArray, Int),
+            _ => array!(LongArray, Long)
+        })
+    }
+    /// Reads an unquoted value, which is a number if it looks like one and a string otherwise
+    fn unquoted_nbt(&mut self) -> Result<Nbt> {
+        let s = self.unquoted();
+        if s.is_empty() {
+            return self.error("expected a value");
+        }
+        let is_integer = |s: &str| s.strip_prefix('-').unwrap_or(s).chars().all(|c| c.is_ascii_digit());
+        // Rust also parses `inf` and `NaN`, which are strings in SNBT, but numbers too large for their type are read as infinity
+        let is_float = |s: &str| s.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'));
+        let (body, suffix) = s.split_at(s.len() - 1);
+        let number = match suffix.to_ascii_lowercase().as_str() {
+            "b" if is_integer(body) => body.parse().ok().map(Nbt::Byte),
+            "s" if is_integer(body) => body.parse().ok().map(Nbt::Short),
+            "l" if is_integer(body) => body.parse().ok().map(Nbt::Long),
+            "f" if is_float(body) => body.parse().ok().map(Nbt::Float),
+            "d" if is_float(body) => body.parse().ok().map(Nbt::Double),
+            _ if is_integer(s) => s.parse().ok().map(Nbt::Int),
+            _ if s.contains('.') && is_float(s) => s.parse().ok().map(Nbt::Double),
+            _ => None
+        };
+        Ok(match (number, s) {
+            (Some(number), _) => number,
+            (None, "true") => Nbt::Byte(1),
+            (None, "false") => Nbt::Byte(0),
+            (None, s) => Nbt::String(s.to_string())
+        })
+    }
+    fn nbt(&mut self) -> Result<Nbt> {
+        match self.peek() {
+            Some('{') => self.compound_nbt().map(Nbt::Compound),
+            Some('[') => self.list_nbt(),
+            Some('"' | '\'') => self.nbt_string(is_unquoted).map(Nbt::String),
+            _ => self.unquoted_nbt()
+        }
+    }
+    fn path_key(&mut self) -> Result<String> {
+        let start = self.pos;
+        let key = self.nbt_string(is_unquoted_key)?;
+        if self.pos == start {
+            return self.error("expected a key");
+        }
+        Ok(key)
+    }
+    fn nbt_path(&mut self) -> Result<NbtPath> {
+        let mut path = match self.peek() {
+            Some('{') => NbtPath::compound(Nbt::Compound(self.compound_nbt()?)),
+            _ => NbtPath::root(self.path_key()?)
+        };
+        loop {
+            match self.peek() {
+                None => return Ok(path),
+                Some('{') if matches!(path.nodes().last(), Some(NbtPathNode::Key(_))) => path = path.matching(Nbt::Compound(self.compound_nbt()?)),
+                Some('.') => {
+                    self.pos += 1;
+                    path = path.key(self.path_key()?);
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    path = match self.peek() {
+                        Some(']') => path.all(),
+                        Some('{') => path.filter(Nbt::Compound(self.compound_nbt()?)),
+                        _ => path.index(self.parsed("index", |c| c.is_ascii_digit() || c == '-')?)
+                    };
+                    self.expect(']')?;
+                }
+                Some(c) => return self.error(format!("unexpected `{}` in nbt path", c))
+            }
+        }
+    }
+}
+
+impl Nbt {
+    /// Parse SNBT, e.g `{Count:1b,id:"minecraft:stone"}`.
+    /// Anything produced by [`Display`] can be parsed back to equal nbt.
+    /// ```
+    /// # use copper::{nbt, core::nbt::Nbt};
+    /// assert_eq!(Nbt::parse("{a:[1s,2s],b:'x',c:true}"), Ok(nbt!{a: [1i16, 2i16], b: "x", c: true}));
+    /// assert!(Nbt::parse("[1,2b]").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut reader = Reader {input, pos: 0};
+        reader.skip_whitespace();
+        let nbt = reader.nbt()?;
+        reader.skip_whitespace();
+        if reader.pos != input.len() {
+            return reader.error("unexpected characters after nbt");
+        }
+        Ok(nbt)
+    }
+}
+impl FromStr for Nbt {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl NbtPath {
+    /// Parse an nbt path, e.g `Inventory[{Slot:0b}].tag.display.Name`.
+    /// Anything produced by [`Display`] can be parsed back to an equal path.
+    /// ```
+    /// # use copper::{nbt, core::nbt::NbtPath};
+    /// let path = NbtPath::parse("Inventory[{Slot:0b}].tag.display.Name").unwrap();
+    /// assert_eq!(path, NbtPath::root("Inventory").filter(nbt!{Slot: 0i8}).key("tag").key("display").key("Name"));
+    /// assert!(NbtPath::parse("Inventory[").is_err());
+    /// ```
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut reader = Reader {input, pos: 0};
+        reader.nbt_path()
+    }
+}
+impl FromStr for NbtPath {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl FromStr for SelectorType {
+    type Err = ();
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use SelectorType::*;
+        Ok(match s {
+            "s" => S,
+            "p" => P,
+            "e" => E,
+            "a" => A,
+            "r" => R,
+            _ => return Err(())
+        })
+    }
+}
+impl FromStr for SelectorSort {
+    type Err = ();
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use SelectorSort::*;
+        Ok(match s {
+            "nearest" => Nearest,
+            "furthest" => Furthest,
+            "arbitrary" => Arbritrary,
+            "random" => Random,
+            _ => return Err(())
+        })
+    }
+}
+impl FromStr for GameMode {
+    type Err = ();
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use GameMode::*;
+        Ok(match s {
+            "creative" => Creative,
+            "survival" => Survival,
+            "spectator" => Spectator,
+            "adventure" => Adventure,
+            _ => return Err(())
+        })
+    }
+}
+
+impl<'a> Selector<'a> {
+    /// Parse a selector, e.g `@e[type=cow,distance=..5,tag=!seen]`.
+    /// The selector borrows from the input.
+    /// Anything produced by the selector's [`Display`] can be parsed back to an equal selector.
+    /// ```
+    /// # use copper::{prelude::*, core::Selector};
+    /// let selector = Selector::parse("@e[type=cow,distance=..5,tag=!seen]").unwrap();
+    /// assert_eq!(selector, at_e().entity(Entity::Cow, true).distance(..=5.0).tag("seen", false));
+    /// assert!(Selector::parse("@e[foo=bar]").is_err());
+    /// ```
+    pub fn parse(input: &'a str) -> Result<Self> {
+        let mut reader = Reader {input, pos: 0};
+        reader.expect('@')?;
+        let sel = reader.parsed("selector type", |c| c.is_ascii_alphabetic())?;
+        let mut selector = Selector::new(sel);
+        if reader.peek() == Some('[') {
+            reader.pos += 1;
+            reader.skip_whitespace();
+            if reader.peek() == Some(']') {
+                reader.pos += 1;
+            } else {
+                loop {
+                    reader.argument(&mut selector)?;
+                    reader.skip_whitespace();
+                    match reader.peek() {
+                        Some(',') => {reader.pos += 1; reader.skip_whitespace()}
+                        Some(']') => {reader.pos += 1; break}
+                        _ => return reader.error("expected `,` or `]`")
+                    }
+                }
+            }
+        }
+        if reader.pos != input.len() {
+            return reader.error("unexpected characters after selector");
+        }
+        Ok(selector)
+    }
+}
+impl<'a> TryFrom<&'a str> for Selector<'a> {
+    type Error = ParseError;
+    fn try_from(input: &'a str) -> Result<Self> {
+        Self::parse(input)
+    }
+}
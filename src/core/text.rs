@@ -0,0 +1,305 @@
+/*!
+Contains the [`TextComponent`] struct, used for JSON text throughout minecraft.
+
+Create a component with one of its constructors, then style it using the builder pattern:
+```
+# use copper::{core::text::*, prelude::*};
+let text = TextComponent::text("Hello ")
+    .color(TextColor::Gold)
+    .bold(true)
+    .extra(TextComponent::selector(at_s()))
+    .click(ClickEvent::RunCommand("/trigger hello"));
+assert_eq!(text.to_string(), r#"{"text":"Hello ","color":"gold","bold":true,"clickEvent":{"action":"run_command","value":"/trigger hello"},"extra":[{"selector":"@s"}]}"#);
+```
+*/
+
+use std::fmt::Display;
+
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+use crate::minecraft::*;
+use super::{DataTarget, Identifier, ScoreHolder, Selector, nbt::{Nbt, NbtPath}};
+
+fn serialize_display<S: Serializer>(value: &impl Display, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn serialize_data_target<S: Serializer>(target: &DataTarget<'_>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    match target {
+        DataTarget::Block(location) => map.serialize_entry("block", &location.to_string())?,
+        DataTarget::Entity(target) => map.serialize_entry("entity", &target.to_string())?,
+        DataTarget::Storage(id) => map.serialize_entry("storage", id)?
+    }
+    map.end()
+}
+
+/// The score shown by a [`TextContent::Score`]
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct ScoreContent<'a> {
+    /// The holder of the score
+    #[serde(serialize_with = "serialize_display")] pub name: ScoreHolder<'a>,
+    /// The objective of the score
+    pub objective: &'a str
+}
+
+/// The content of a [`TextComponent`]
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum TextContent<'a> {
+    /// Plain text
+    Text {
+        /// The text to show
+        text: &'a str
+    },
+    /// A translated string
+    Translate {
+        /// The translation key
+        translate: &'a str,
+        /// The arguments used by the translation
+        #[serde(skip_serializing_if = "Vec::is_empty")] with: Vec<TextComponent<'a>>
+    },
+    /// A score
+    Score {
+        /// The score to show
+        score: ScoreContent<'a>
+    },
+    /// The names of the entities matched by a selector
+    Selector {
+        /// The selector to use
+        #[serde(serialize_with = "serialize_display")] selector: Box<Selector<'a>>
+    },
+    /// The key bound to a control
+    Keybind {
+        /// The id of the control, e.g `key.jump`
+        keybind: &'a str
+    },
+    /// Nbt data from a block, entity or storage
+    Nbt {
+        /// The nbt path to show
+        nbt: NbtPath,
+        /// Where the nbt comes from
+        #[serde(flatten, serialize_with = "serialize_data_target")] source: DataTarget<'a>,
+        /// Whether to interpret the nbt as a text component
+        #[serde(skip_serializing_if = "std::ops::Not::not")] interpret: bool
+    }
+}
+
+/// The color of a [`TextComponent`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[allow(missing_docs)]
+pub enum TextColor {
+    Black, DarkBlue, DarkGreen, DarkAqua, DarkRed, DarkPurple, Gold, Gray, DarkGray, Blue, Green, Aqua, Red, LightPurple, Yellow, White,
+    /// A hex color, e.g `0xff8800`
+    Hex(u32)
+}
+impl Display for TextColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TextColor::*;
+        write!(f, "{}", match self {
+            Black => "black",
+            DarkBlue => "dark_blue",
+            DarkGreen => "dark_green",
+            DarkAqua => "dark_aqua",
+            DarkRed => "dark_red",
+            DarkPurple => "dark_purple",
+            Gold => "gold",
+            Gray => "gray",
+            DarkGray => "dark_gray",
+            Blue => "blue",
+            Green => "green",
+            Aqua => "aqua",
+            Red => "red",
+            LightPurple => "light_purple",
+            Yellow => "yellow",
+            White => "white",
+            Hex(hex) => return write!(f, "#{:06x}", hex)
+        })
+    }
+}
+impl Serialize for TextColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        serializer.collect_str(self)
+    }
+}
+
+/// An action run when a [`TextComponent`] is clicked
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(tag = "action", content = "value", rename_all = "snake_case")]
+pub enum ClickEvent<'a> {
+    /// Opens a url
+    OpenUrl(&'a str),
+    /// Runs a command as the player
+    RunCommand(&'a str),
+    /// Puts a command into the player's chat box
+    SuggestCommand(&'a str),
+    /// Changes the page of a book
+    ChangePage(i64),
+    /// Copies text to the clipboard
+    CopyToClipboard(&'a str)
+}
+
+/// A tooltip shown when a [`TextComponent`] is hovered over
+#[derive(Serialize, Debug, PartialEq, Clone)]
+#[serde(tag = "action", content = "contents", rename_all = "snake_case")]
+pub enum HoverEvent<'a> {
+    /// Shows text
+    ShowText(Box<TextComponent<'a>>),
+    /// Shows an item's tooltip
+    ShowItem {
+        /// The item to show
+        id: Item,
+        /// The size of the stack
+        #[serde(skip_serializing_if = "Option::is_none")] count: Option<i64>,
+        /// The nbt of the item
+        #[serde(skip_serializing_if = "Option::is_none")] tag: Option<Nbt>
+    },
+    /// Shows an entity's name, type and uuid
+    ShowEntity {
+        /// The type of the entity
+        #[serde(rename = "type")] ty: Entity,
+        /// The uuid of the entity
+        id: &'a str,
+        /// The name of the entity
+        #[serde(skip_serializing_if = "Option::is_none")] name: Option<Box<TextComponent<'a>>>
+    }
+}
+
+/// A JSON text component. See the [module level docs](self) for usage.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct TextComponent<'a> {
+    /// The content of the component
+    #[serde(flatten)] pub content: TextContent<'a>,
+    /// The color of the text
+    #[serde(skip_serializing_if = "Option::is_none")] pub color: Option<TextColor>,
+    /// The font of the text
+    #[serde(skip_serializing_if = "Option::is_none")] pub font: Option<Identifier<'a, 'a>>,
+    /// Whether the text is bold
+    #[serde(skip_serializing_if = "Option::is_none")] pub bold: Option<bool>,
+    /// Whether the text is italic
+    #[serde(skip_serializing_if = "Option::is_none")] pub italic: Option<bool>,
+    /// Whether the text is underlined
+    #[serde(skip_serializing_if = "Option::is_none")] pub underlined: Option<bool>,
+    /// Whether the text is struck through
+    #[serde(skip_serializing_if = "Option::is_none")] pub strikethrough: Option<bool>,
+    /// Whether the text is obfuscated
+    #[serde(skip_serializing_if = "Option::is_none")] pub obfuscated: Option<bool>,
+    /// Text inserted into the chat box when the component is shift-clicked
+    #[serde(skip_serializing_if = "Option::is_none")] pub insertion: Option<&'a str>,
+    /// An action run when the component is clicked
+    #[serde(rename = "clickEvent", skip_serializing_if = "Option::is_none")] pub click_event: Option<ClickEvent<'a>>,
+    /// A tooltip shown when the component is hovered over
+    #[serde(rename = "hoverEvent", skip_serializing_if = "Option::is_none")] pub hover_event: Option<HoverEvent<'a>>,
+    /// Children of the component, which inherit its style
+    #[serde(skip_serializing_if = "Vec::is_empty")] pub extra: Vec<TextComponent<'a>>
+}
+impl<'a> TextComponent<'a> {
+    /// Create a component from its content, without any styling
+    pub fn new(content: TextContent<'a>) -> Self {
+        Self {
+            content,
+            color: None,
+            font: None,
+            bold: None,
+            italic: None,
+            underlined: None,
+            strikethrough: None,
+            obfuscated: None,
+            insertion: None,
+            click_event: None,
+            hover_event: None,
+            extra: Vec::new()
+        }
+    }
+    /// Create a plain text component
+    pub fn text(text: &'a str) -> Self {
+        Self::new(TextContent::Text {text})
+    }
+    /// Create a translated component, with the given arguments
+    pub fn translate(translate: &'a str, with: Vec<TextComponent<'a>>) -> Self {
+        Self::new(TextContent::Translate {translate, with})
+    }
+    /// Create a component showing a score
+    pub fn score(name: impl Into<ScoreHolder<'a>>, objective: &'a str) -> Self {
+        Self::new(TextContent::Score {score: ScoreContent {name: name.into(), objective}})
+    }
+    /// Create a component showing the names of the entities matched by a selector
+    pub fn selector(selector: Selector<'a>) -> Self {
+        Self::new(TextContent::Selector {selector: Box::new(selector)})
+    }
+    /// Create a component showing the key bound to a control
+    pub fn keybind(keybind: &'a str) -> Self {
+        Self::new(TextContent::Keybind {keybind})
+    }
+    /// Create a component showing nbt data
+    pub fn nbt(nbt: NbtPath, source: DataTarget<'a>, interpret: bool) -> Self {
+        Self::new(TextContent::Nbt {nbt, source, interpret})
+    }
+    /// Sets the `color` of this component.
+    pub fn color(mut self, color: TextColor) -> Self {
+        self.color = Some(color);
+        self
+    }
+    /// Sets the `font` of this component.
+    pub fn font(mut self, font: Identifier<'a, 'a>) -> Self {
+        self.font = Some(font);
+        self
+    }
+    /// Sets whether this component is `bold`.
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+    /// Sets whether this component is `italic`.
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+    /// Sets whether this component is `underlined`.
+    pub fn underlined(mut self, underlined: bool) -> Self {
+        self.underlined = Some(underlined);
+        self
+    }
+    /// Sets whether this component is `strikethrough`.
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+    /// Sets whether this component is `obfuscated`.
+    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+        self.obfuscated = Some(obfuscated);
+        self
+    }
+    /// Sets the `insertion` of this component.
+    pub fn insertion(mut self, insertion: &'a str) -> Self {
+        self.insertion = Some(insertion);
+        self
+    }
+    /// Sets the `clickEvent` of this component.
+    pub fn click(mut self, event: ClickEvent<'a>) -> Self {
+        self.click_event = Some(event);
+        self
+    }
+    /// Sets the `hoverEvent` of this component.
+    pub fn hover(mut self, event: HoverEvent<'a>) -> Self {
+        self.hover_event = Some(event);
+        self
+    }
+    /// Adds a child to the `extra` of this component.
+    pub fn extra(mut self, child: TextComponent<'a>) -> Self {
+        self.extra.push(child);
+        self
+    }
+}
+impl<'a> From<&'a str> for TextComponent<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::text(text)
+    }
+}
+impl Display for TextComponent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
@@ -0,0 +1,217 @@
+/*!
+Contains the [`Advancement`] struct.
+Advancements are passed to a datapack via [`Datapack::advancement`](crate::datapack::Datapack::advancement).
+
+Each criterion of an advancement is a [`Trigger`]. The conditions of a trigger are config structs,
+built from the same predicates used in [`predicate`](super::predicate), so you can use `..default()` with them. For example:
+```
+# use copper::prelude::*;
+# use copper::datapack::{advancement::*, predicate::*};
+Advancement {
+    criteria: &[("got_dirt", Trigger::InventoryChanged(InventoryChangedConditions {
+        items: Some(&[ItemPredicate {
+            item: Some(Item::Dirt),
+            ..default()
+        }]),
+        ..default()
+    }))],
+    rewards: Some(AdvancementRewards {
+        function: Some(id!(foo:got_dirt)),
+        ..default()
+    }),
+    ..default()
+};
+```
+*/
+
+use serde::{Serialize, Serializer};
+
+use crate::{core::{Identifier, TupleMapSerializer, nbt::Nbt, serialize_tuple_map, text::TextComponent}, minecraft::*};
+
+use super::predicate::{BlockstateValue, DamagePredicate, EffectPredicate, EntityPredicate, ItemPredicate, LocationPredicate, OptionalRange};
+
+/// The frame around an advancement's icon
+#[derive(Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum AdvancementFrame {
+    Task, Goal, Challenge
+}
+
+/// The icon of an advancement
+#[derive(Serialize)]
+pub struct AdvancementIcon {
+    /// The item to display
+    pub item: Item,
+    /// The nbt of the item
+    #[serde(skip_serializing_if = "Option::is_none")] pub nbt: Option<Nbt>
+}
+
+/// How an advancement is displayed. If this is omitted the advancement is hidden.
+#[derive(Serialize)]
+pub struct AdvancementDisplay<'a, 'b> {
+    /// The icon of the advancement
+    pub icon: AdvancementIcon,
+    /// The title of the advancement
+    pub title: TextComponent<'a>,
+    /// The description of the advancement
+    pub description: TextComponent<'a>,
+    /// The frame around the icon, defaults to [`AdvancementFrame::Task`]
+    #[serde(skip_serializing_if = "Option::is_none")] pub frame: Option<AdvancementFrame>,
+    /// The background texture, only used by root advancements
+    #[serde(skip_serializing_if = "Option::is_none")] pub background: Option<Identifier<'a, 'b>>,
+    /// Whether to show a toast when the advancement is completed
+    #[serde(skip_serializing_if = "Option::is_none")] pub show_toast: Option<bool>,
+    /// Whether to announce the advancement in chat
+    #[serde(skip_serializing_if = "Option::is_none")] pub announce_to_chat: Option<bool>,
+    /// Whether to hide the advancement until it is completed
+    #[serde(skip_serializing_if = "Option::is_none")] pub hidden: Option<bool>
+}
+
+config_struct! {
+    struct AdvancementRewards<'a, 'b> where "Rewards given when an advancement is completed" {
+        recipes: &'a [Identifier<'b, 'b>] where "Recipes to unlock",
+        loot: &'a [Identifier<'b, 'b>] where "Loot tables to give to the player",
+        experience: i64 where "Experience to give to the player",
+        function: Identifier<'a, 'b> where "A function to run as the player"
+    }
+    struct InventorySlots where "Checks the slots of an inventory" {
+        empty: OptionalRange<i64> where "The number of empty slots",
+        full: OptionalRange<i64> where "The number of full slots",
+        occupied: OptionalRange<i64> where "The number of occupied slots"
+    }
+    struct InventoryChangedConditions<'a, 'b> where "Conditions for [`Trigger::InventoryChanged`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        items: &'a [ItemPredicate<'b, 'b>] where "Items which must all be in the inventory",
+        slots: InventorySlots where "Checks the slots of the inventory"
+    }
+    struct KillConditions<'a, 'b> where "Conditions for [`Trigger::PlayerKilledEntity`] and [`Trigger::EntityKilledPlayer`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        entity: EntityPredicate<'a, 'b> where "Checks the other entity",
+        killing_blow: DamagePredicate<'a, 'b> where "Checks the damage that killed the entity"
+    }
+    struct LocationConditions<'a, 'b> where "Conditions for triggers which check the player's location, like [`Trigger::Location`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        location: LocationPredicate<'a, 'b> where "Checks the location of the player"
+    }
+    struct ItemConditions<'a, 'b> where "Conditions for triggers which check a single item, like [`Trigger::ConsumeItem`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        item: ItemPredicate<'a, 'b> where "Checks the item"
+    }
+    struct EntityConditions<'a, 'b> where "Conditions for triggers which check a single entity, like [`Trigger::SummonedEntity`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        entity: EntityPredicate<'a, 'b> where "Checks the entity"
+    }
+    struct EnterBlockConditions<'a, 'b> where "Conditions for [`Trigger::EnterBlock`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        block: Block where "The block entered",
+        {"serialize_tuple_map"} state: &'a [(&'b str, BlockstateValue<'b>)] where "Block states to check"
+    }
+    struct PlacedBlockConditions<'a, 'b> where "Conditions for [`Trigger::PlacedBlock`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        block: Block where "The block placed",
+        {"serialize_tuple_map"} state: &'a [(&'b str, BlockstateValue<'b>)] where "Block states to check",
+        item: ItemPredicate<'a, 'b> where "Checks the item used to place the block",
+        location: LocationPredicate<'a, 'b> where "Checks the location of the block"
+    }
+    struct ItemUsedOnBlockConditions<'a, 'b> where "Conditions for [`Trigger::ItemUsedOnBlock`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        item: ItemPredicate<'a, 'b> where "Checks the item used",
+        location: LocationPredicate<'a, 'b> where "Checks the location of the block"
+    }
+    struct ChangedDimensionConditions<'a, 'b> where "Conditions for [`Trigger::ChangedDimension`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        from: Identifier<'a, 'b> where "The dimension travelled from",
+        to: Identifier<'a, 'b> where "The dimension travelled to"
+    }
+    struct RecipeUnlockedConditions<'a, 'b> where "Conditions for [`Trigger::RecipeUnlocked`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        recipe: Identifier<'a, 'b> where "The recipe unlocked"
+    }
+    struct EffectsChangedConditions<'a, 'b> where "Conditions for [`Trigger::EffectsChanged`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        {"serialize_tuple_map"} effects: &'a [(Effect, EffectPredicate)] where "A list of status effects"
+    }
+    struct BredAnimalsConditions<'a, 'b> where "Conditions for [`Trigger::BredAnimals`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        parent: EntityPredicate<'a, 'b> where "Checks the first parent",
+        partner: EntityPredicate<'a, 'b> where "Checks the second parent",
+        child: EntityPredicate<'a, 'b> where "Checks the child"
+    }
+    struct EnchantedItemConditions<'a, 'b> where "Conditions for [`Trigger::EnchantedItem`]" {
+        player: EntityPredicate<'a, 'b> where "Checks the player",
+        item: ItemPredicate<'a, 'b> where "Checks the item after it was enchanted",
+        levels: OptionalRange<i64> where "The levels spent"
+    }
+}
+
+/// A trigger used as a criterion of an [`Advancement`]
+#[derive(Serialize)]
+#[serde(tag = "trigger", content = "conditions", rename_all = "snake_case")]
+pub enum Trigger<'a, 'b> {
+    /// Never triggers; the criterion can only be granted with `/advancement`
+    Impossible,
+    /// Triggers every tick
+    Tick,
+    /// Triggers when the player's inventory changes
+    InventoryChanged(InventoryChangedConditions<'a, 'b>),
+    /// Triggers when the player kills an entity
+    PlayerKilledEntity(KillConditions<'a, 'b>),
+    /// Triggers when an entity kills the player
+    EntityKilledPlayer(KillConditions<'a, 'b>),
+    /// Triggers every second, checking the player's location
+    Location(LocationConditions<'a, 'b>),
+    /// Triggers when the player enters a bed
+    SleptInBed(LocationConditions<'a, 'b>),
+    /// Triggers when the player wins a raid
+    HeroOfTheVillage(LocationConditions<'a, 'b>),
+    /// Triggers when the player kills a raid captain
+    VoluntaryExile(LocationConditions<'a, 'b>),
+    /// Triggers when the player finishes consuming an item
+    ConsumeItem(ItemConditions<'a, 'b>),
+    /// Triggers when the player fills a bucket
+    FilledBucket(ItemConditions<'a, 'b>),
+    /// Triggers when the player is saved by a totem
+    UsedTotem(ItemConditions<'a, 'b>),
+    /// Triggers when the player summons an entity (e.g an iron golem)
+    SummonedEntity(EntityConditions<'a, 'b>),
+    /// Triggers when the player tames an animal
+    TameAnimal(EntityConditions<'a, 'b>),
+    /// Triggers when the player stands in a block
+    EnterBlock(EnterBlockConditions<'a, 'b>),
+    /// Triggers when the player places a block
+    PlacedBlock(PlacedBlockConditions<'a, 'b>),
+    /// Triggers when the player uses an item on a block
+    ItemUsedOnBlock(ItemUsedOnBlockConditions<'a, 'b>),
+    /// Triggers when the player changes dimension
+    ChangedDimension(ChangedDimensionConditions<'a, 'b>),
+    /// Triggers when the player unlocks a recipe
+    RecipeUnlocked(RecipeUnlockedConditions<'a, 'b>),
+    /// Triggers when the player's effects change
+    EffectsChanged(EffectsChangedConditions<'a, 'b>),
+    /// Triggers when the player breeds two animals
+    BredAnimals(BredAnimalsConditions<'a, 'b>),
+    /// Triggers when the player enchants an item
+    EnchantedItem(EnchantedItemConditions<'a, 'b>)
+}
+
+fn serialize_criteria<S: Serializer>(criteria: &&[(&str, Trigger<'_, '_>)], serializer: S) -> Result<S::Ok, S::Error> {
+    TupleMapSerializer(criteria).serialize(serializer)
+}
+
+/// An advancement. Use [`Datapack::advancement`](crate::datapack::Datapack::advancement).
+#[derive(Serialize, Default)]
+pub struct Advancement<'a, 'b> {
+    /// The parent of the advancement. If omitted, this is a root advancement
+    #[serde(skip_serializing_if = "Option::is_none")] pub parent: Option<Identifier<'a, 'b>>,
+    /// How the advancement is displayed
+    #[serde(skip_serializing_if = "Option::is_none")] pub display: Option<AdvancementDisplay<'a, 'b>>,
+    /// The criteria to be completed, in the form `(name, trigger)`
+    #[serde(serialize_with = "serialize_criteria")] pub criteria: &'a [(&'b str, Trigger<'b, 'b>)],
+    /// Which criteria are required, as a list of lists of names.
+    /// Each inner list is joined with or, and the outer list with and.
+    /// If omitted, all criteria are required.
+    #[serde(skip_serializing_if = "Option::is_none")] pub requirements: Option<&'a [&'b [&'b str]]>,
+    /// The rewards given on completion
+    #[serde(skip_serializing_if = "Option::is_none")] pub rewards: Option<AdvancementRewards<'a, 'b>>
+}
@@ -29,19 +29,61 @@ func.run(EffectClear{effect: Some(Effect::Blindness), ..EffectClear()}); // effe
 ```
 */
 
-use std::{fs::File, fs, path::Path};
+use std::{cell::RefCell, collections::BTreeMap, fmt::Display, fs::File, fs, mem, ops::{Not, Range}, path::{Path, PathBuf}, rc::Rc};
 use std::io::Write;
 
-use crate::{core::{Coordinates, Identifier, Selector, sel::at_s}, score::ScoreValue};
+use crate::{core::{Coordinate, Coordinates, DataTarget, Identifier, NumberRange, ScoreHolder, Selector, nbt::{Nbt, NbtPath, write_compound}, sel::at_s, text::{TextColor, TextComponent}}, score::{Objective, ScoreValue, TEMP, score_int}, storage::StorageValue};
 use crate::minecraft::*;
 
+use super::PackState;
+
+/// The output of a [`Function`], shared with the [`ScoreValue`]s it creates.
+pub (crate) struct Output {
+    prefix: String,
+    file: File,
+    data: PathBuf,
+    namespace: String,
+    path: String,
+    children: usize
+}
+impl Output {
+    fn start(&mut self) {
+        write!(self.file, "{}{}", self.prefix, if self.prefix.is_empty() {""} else {" run "}).unwrap();
+    }
+    /// Write a [`Command`], applying the current execute prefix.
+    pub (crate) fn run(&mut self, cmd: impl Command) {
+        self.start();
+        cmd.output(&mut self.file);
+        writeln!(self.file).unwrap();
+    }
+    /// Write a raw command, applying the current execute prefix.
+    pub (crate) fn line(&mut self, line: impl Display) {
+        self.start();
+        writeln!(self.file, "{}", line).unwrap();
+    }
+    /// Write an `execute` command, adding `subcommands` to the current execute prefix.
+    pub (crate) fn execute(&mut self, subcommands: impl Display) {
+        writeln!(self.file, "{} {}", if self.prefix.is_empty() {"execute"} else {&self.prefix}, subcommands).unwrap();
+    }
+    /// Write an [`ExecuteRun`], adding its subcommands to the current execute prefix.
+    pub (crate) fn execute_run(&mut self, command: ExecuteRun<'_, impl Command>) {
+        write!(self.file, "{}", if self.prefix.is_empty() {"execute"} else {&self.prefix}).unwrap();
+        for subcommand in &command.execute.subcommands {
+            write!(self.file, " {}", subcommand).unwrap();
+        }
+        write!(self.file, " run ").unwrap();
+        command.command.output(&mut self.file);
+        writeln!(self.file).unwrap();
+    }
+}
+
 /// A handle to an mcfunction file, created with [`Datapack::function()`](super::Datapack::function)
 pub struct Function {
-    prefix: String,
-    out: File
+    out: Rc<RefCell<Output>>,
+    pack: Rc<RefCell<PackState>>
 }
 impl Function {
-    pub (super) fn new(path: impl AsRef<Path>, id: Identifier<'_, '_>) -> Self {
+    pub (super) fn new(path: impl AsRef<Path>, id: Identifier<'_, '_>, pack: Rc<RefCell<PackState>>) -> Self {
         let mut functions = path.as_ref().join(id.namespace).join("functions");
         for folder in id.folders {
             functions = functions.join(folder);
@@ -49,20 +91,293 @@ impl Function {
         fs::create_dir_all(&functions).unwrap();
         let mut out_path = functions.join(id.id);
         out_path.set_extension("mcfunction");
+        let id = id.to_string();
+        pack.borrow_mut().functions.insert(id.clone());
+        let (namespace, function_path) = id.split_once(':').unwrap();
         Self {
-            prefix: String::new(),
-            out: File::create(out_path).unwrap()
+            out: Rc::new(RefCell::new(Output {
+                prefix: String::new(),
+                file: File::create(out_path).unwrap(),
+                data: path.as_ref().to_path_buf(),
+                namespace: namespace.to_string(),
+                path: function_path.to_string(),
+                children: 0
+            })),
+            pack
+        }
+    }
+    /// Write `body` into a new generated function, `<parent>/__<kind>_<n>`, returning its id and file.
+    /// Generated functions are numbered in the order they're created, so the output is stable.
+    fn child(&mut self, kind: &str, body: impl FnOnce(&mut Self)) -> (String, PathBuf) {
+        let (id, path) = {
+            let mut out = self.out.borrow_mut();
+            let path = format!("{}/__{}_{}", out.path, kind, out.children);
+            out.children += 1;
+            (format!("{}:{}", out.namespace, path), out.data.join(&out.namespace).join("functions").join(format!("{}.mcfunction", path)))
+        };
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        self.pack.borrow_mut().functions.insert(id.clone());
+        let (file, prefix) = {
+            let mut out = self.out.borrow_mut();
+            (mem::replace(&mut out.file, File::create(&path).unwrap()), mem::take(&mut out.prefix))
+        };
+        body(self);
+        let mut out = self.out.borrow_mut();
+        out.file = file;
+        out.prefix = prefix;
+        (id, path)
+    }
+    /// Run `body` without the execute prefix. Under a prefix, `body` is written into a generated function which is called through it,
+    /// since a prefix runs each command for every entity before the next command starts.
+    /// This way, sequences which keep state in a global score (like `#if`) run in full for each entity.
+    fn unprefixed(&mut self, body: impl FnOnce(&mut Self)) {
+        if self.out.borrow().prefix.is_empty() {
+            body(self);
+        } else {
+            self.call(body);
         }
     }
     /// Run a [`Command`].
     pub fn run(&mut self, cmd: impl Command) {
-        write!(self.out, "{}{}", self.prefix, if self.prefix.is_empty() {""} else {" run "}).unwrap();
-        cmd.output(&mut self.out);
-        writeln!(self.out).unwrap();
+        let references = cmd.references();
+        if !references.is_empty() {
+            let out = self.out.borrow();
+            let from = format!("{}:{}", out.namespace, out.path);
+            let mut pack = self.pack.borrow_mut();
+            for reference in references {
+                pack.reference(reference, &from);
+            }
+        }
+        self.out.borrow_mut().run(cmd);
+    }
+    /// Run every command in `body` with an [`Execute`] prefix, i.e `execute <subcommands...> run <command>`.
+    /// This also applies to [`ScoreValue`]s, and nested calls add their subcommands to the prefix.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// func.with_execute(Execute().as_(at_a()), |func| {
+    ///     func.run(Kill()); // execute as @a run kill
+    ///     func.with_execute(Execute().at(at_s()), |func| {
+    ///         func.run(Setblock(loc!(~ ~ ~), Block::Stone)); // execute as @a at @s run setblock ~ ~ ~ stone
+    ///     });
+    /// });
+    /// ```
+    pub fn with_execute(&mut self, execute: Execute<'_>, body: impl FnOnce(&mut Self)) {
+        let outer = self.out.borrow().prefix.clone();
+        let mut prefix = if outer.is_empty() {String::from("execute")} else {outer.clone()};
+        for subcommand in &execute.subcommands {
+            prefix += &format!(" {}", subcommand);
+        }
+        self.out.borrow_mut().prefix = prefix;
+        body(self);
+        self.out.borrow_mut().prefix = outer;
+    }
+    /// Run `body` if `condition` passes. Call [`else_`](IfElse::else_) on the result to add an else branch.
+    /// Each branch is written into a generated function. With an else branch, the condition is stored in a guard score first,
+    /// so the if branch can't cause the else branch to run too. Under [`with_execute`](Self::with_execute),
+    /// the guard and the dispatch are written into another generated function, so each entity takes its own branch:
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// let global = pack.objective("global", "dummy", None);
+    /// let x = func.score("#x", &global);
+    /// func.if_(x.eq_const(0), |f| f.run(Say("zero"))).else_(|f| f.run(Say("not zero")));
+    /// // execute store success score #if copper.temp if score #x global matches 0
+    /// // execute if score #if copper.temp matches 1 run function test:func/__if_0
+    /// // execute if score #if copper.temp matches 0 run function test:func/__else_1
+    /// ```
+    pub fn if_(&mut self, condition: Condition<'_>, body: impl FnOnce(&mut Self)) -> IfElse<'_> {
+        let (then, then_path) = self.child("if", body);
+        IfElse {func: self, condition: Some(condition.to_string()), then, then_path}
+    }
+    /// Run the `body` for the case matching `score`, out of every value in `cases`.
+    /// The cases are dispatched through a binary search tree of generated functions, so only about `log2(cases.len())` checks are run.
+    /// The score is copied first, so the cases can modify it. Under [`with_execute`](Self::with_execute),
+    /// the copy and the dispatch are written into another generated function, so each entity takes its own case.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// let global = pack.objective("global", "dummy", None);
+    /// let state = func.score("#state", &global);
+    /// func.switch(&state, 0..4, |f, case| f.run(Say(["a", "b", "c", "d"][case as usize])));
+    /// // scoreboard players operation #switch copper.temp = #state global
+    /// // execute if score #switch copper.temp matches 0..1 run function test:func/__switch_0
+    /// // execute if score #switch copper.temp matches 2..3 run function test:func/__switch_3
+    /// ```
+    /// # Panics
+    /// Panics if the cases don't fit in a score, which is a 32-bit integer.
+    pub fn switch(&mut self, score: &ScoreValue<'_>, cases: Range<i64>, mut body: impl FnMut(&mut Self, i64)) {
+        if cases.is_empty() {
+            return;
+        }
+        score_int(cases.start);
+        score_int(cases.end - 1);
+        self.pack.borrow_mut().internal_objective(TEMP);
+        self.unprefixed(|f| {
+            f.out.borrow_mut().line(format_args!("scoreboard players operation #switch {} = {} {}", TEMP, score.name, score.objective));
+            f.switch_node(cases, &mut body);
+        });
+    }
+    fn switch_node(&mut self, cases: Range<i64>, body: &mut impl FnMut(&mut Self, i64)) {
+        let halves = if cases.end - cases.start == 1 {
+            vec![cases]
+        } else {
+            let middle = cases.start + (cases.end - cases.start) / 2;
+            vec![cases.start..middle, middle..cases.end]
+        };
+        for half in halves {
+            let (id, _) = if half.end - half.start == 1 {
+                self.child("case", |f| {
+                    body(f, half.start);
+                    // Restore the copy, in case the body changed it, so no other case matches
+                    f.out.borrow_mut().line(format_args!("scoreboard players set #switch {} {}", TEMP, half.start));
+                })
+            } else {
+                self.child("switch", |f| f.switch_node(half.clone(), body))
+            };
+            let range = NumberRange::from(half.start..=half.end - 1);
+            self.out.borrow_mut().execute(format_args!("if score #switch {} matches {} run function {}", TEMP, range, id));
+        }
+    }
+    /// Run `body` repeatedly while `condition` passes, checking it before each iteration.
+    /// The body is written into a generated function which calls itself at the end:
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// let global = pack.objective("global", "dummy", None);
+    /// let x = func.score("#x", &global);
+    /// func.while_(x.gt(&x), |f| f.run(Say("loop")));
+    /// // execute if score #x global > #x global run function test:func/__while_0
+    /// // In test:func/__while_0:
+    /// // say loop
+    /// // execute if score #x global > #x global run function test:func/__while_0
+    /// ```
+    /// The loop doesn't count its iterations, so `body` is responsible for eventually making `condition` fail.
+    /// The whole loop runs in one tick, so it counts towards the `maxCommandChainLength` gamerule (65536 commands by default),
+    /// together with everything else run in that tick. If it's exceeded, the game stops running commands without any error.
+    /// Each iteration costs the commands in `body`, plus one for the check. Longer loops should be spread over several ticks with `schedule`.
+    pub fn while_(&mut self, condition: Condition<'_>, body: impl FnOnce(&mut Self)) {
+        let (id, path) = self.child("while", body);
+        append(&path, format_args!("execute {} run function {}", condition, id));
+        self.unprefixed(|f| f.out.borrow_mut().execute(format_args!("{} run function {}", condition, id)));
+    }
+    /// Run `body` once for each value of `score` in `range`, counting up from `range.start`.
+    /// After the loop, `score` is `range.end`, unless the range was empty.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// let global = pack.objective("global", "dummy", None);
+    /// let i = func.score("#i", &global);
+    /// func.for_range(&i, 0..10, |f| f.run(Say("loop")));
+    /// // scoreboard players set #i global 0
+    /// // execute if score #i global matches ..9 run function test:func/__for_0
+    /// // In test:func/__for_0:
+    /// // say loop
+    /// // scoreboard players add #i global 1
+    /// // execute if score #i global matches ..9 run function test:func/__for_0
+    /// ```
+    /// Like [`while_`](Self::while_), the loop must fit in `maxCommandChainLength`. Each iteration costs two commands more than `body`.
+    /// # Panics
+    /// Panics if the range doesn't fit in a score, which is a 32-bit integer.
+    pub fn for_range(&mut self, score: &ScoreValue<'_>, range: Range<i64>, body: impl FnOnce(&mut Self)) {
+        if range.is_empty() {
+            return;
+        }
+        let (start, last) = (score_int(range.start), score_int(range.end - 1));
+        let condition = format!("if score {} {} matches ..{}", score.name, score.objective, last);
+        self.unprefixed(|func| {
+            let (id, path) = func.child("for", |f| {
+                body(f);
+                f.out.borrow_mut().line(format_args!("scoreboard players add {} {} 1", score.name, score.objective));
+            });
+            append(&path, format_args!("execute {} run function {}", condition, id));
+            let mut out = func.out.borrow_mut();
+            out.line(format_args!("scoreboard players set {} {} {}", score.name, score.objective, start));
+            out.execute(format_args!("{} run function {}", condition, id));
+        });
+    }
+    /// Write `body` into a generated function, `<parent>/__anon_<n>`, and call it, returning its id.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// func.with_execute(Execute().as_(at_a()).at(at_s()), |f| {
+    ///     f.call(|f| {
+    ///         f.run(Say("hello"));
+    ///         f.run(Say("world"));
+    ///     });
+    /// });
+    /// // execute as @a at @s run function test:func/__anon_0
+    /// ```
+    pub fn call(&mut self, body: impl FnOnce(&mut Self)) -> String {
+        let (id, _) = self.child("anon", body);
+        self.out.borrow_mut().line(format_args!("function {}", id));
+        id
+    }
+    /// Write `body` into a generated function, `<parent>/__anon_<n>`, and schedule it to run in `ticks` ticks, returning its id.
+    /// The function runs as the server, at the world spawn, so it can't use `@s`.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// func.schedule(20, |f| f.run(Say("a second later")));
+    /// // schedule function test:func/__anon_0 20t
+    /// ```
+    pub fn schedule(&mut self, ticks: u64, body: impl FnOnce(&mut Self)) -> String {
+        let (id, _) = self.child("anon", body);
+        self.out.borrow_mut().line(format_args!("schedule function {} {}t", id, ticks));
+        id
     }
     /// Create a [`ScoreValue`], given its name and objective.
-    pub fn score<'a>(&mut self, name: &'a str, objective: &'a str) -> ScoreValue<'a> {
-        ScoreValue::new(name, objective, self.out.try_clone().unwrap())
+    pub fn score<'a>(&mut self, name: &'a str, objective: &'a Objective) -> ScoreValue<'a> {
+        ScoreValue::new(name, objective.name(), self.out.clone(), self.pack.clone())
+    }
+    /// Create a [`StorageValue`], given its storage and path.
+    pub fn storage<'a>(&mut self, storage: Identifier<'a, 'a>, path: NbtPath) -> StorageValue<'a> {
+        StorageValue::new(storage, path, self.out.clone())
+    }
+}
+
+/// Add a line to the end of a function which has already been written
+fn append(path: &Path, line: impl Display) {
+    let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+    writeln!(file, "{}", line).unwrap();
+}
+
+/// An if statement, created by [`Function::if_`]. The commands are written when this is dropped, or by [`else_`](Self::else_).
+pub struct IfElse<'f> {
+    func: &'f mut Function,
+    condition: Option<String>,
+    then: String,
+    then_path: PathBuf
+}
+impl IfElse<'_> {
+    /// Run `body` if the condition failed
+    pub fn else_(mut self, body: impl FnOnce(&mut Function)) {
+        let condition = self.condition.take().unwrap();
+        let (otherwise, _) = self.func.child("else", body);
+        self.func.pack.borrow_mut().internal_objective(TEMP);
+        // Restore the guard at the end of the if branch, in case a nested if statement overwrote it
+        append(&self.then_path, format_args!("scoreboard players set #if {} 1", TEMP));
+        let then = &self.then;
+        self.func.unprefixed(|func| {
+            let mut out = func.out.borrow_mut();
+            out.execute(format_args!("store success score #if {} {}", TEMP, condition));
+            out.execute(format_args!("if score #if {} matches 1 run function {}", TEMP, then));
+            out.execute(format_args!("if score #if {} matches 0 run function {}", TEMP, otherwise));
+        });
+    }
+}
+impl Drop for IfElse<'_> {
+    fn drop(&mut self) {
+        if let Some(condition) = self.condition.take() {
+            self.func.out.borrow_mut().execute(format_args!("{} run function {}", condition, self.then));
+        }
     }
 }
 
@@ -70,6 +385,16 @@ impl Function {
 pub trait Command {
     /// Output to a [`Write`]
     fn output(self, out: &mut impl Write);
+    /// The functions and function tags this command refers to, e.g `foo:bar` or `#foo:bar`.
+    /// These are checked when the [`Datapack`](super::Datapack) is finished, see [`Datapack::finish`](super::Datapack::finish).
+    fn references(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Render a reference to a function or function tag
+fn function_reference(function: &Identifier<'_, '_>, tag: bool) -> String {
+    format!("{}{}", if tag {"#"} else {""}, function)
 }
 
 pub use command::*;
@@ -203,4 +528,817 @@ mod command {
             }
         }
     }
+
+    /// The `tellraw` command.  
+    /// Syntax: `tellraw <targets> <message>`
+    pub struct Tellraw<'a> {
+        pub targets: Selector<'a>,
+        pub message: TextComponent<'a>
+    }
+    pub fn Tellraw<'a>(targets: Selector<'a>, message: TextComponent<'a>) -> Tellraw<'a> {
+        Tellraw {targets, message}
+    }
+    impl Command for Tellraw<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "tellraw {} {}", self.targets, self.message).unwrap();
+        }
+    }
+
+    /// An action of the `title` command
+    pub enum TitleAction<'a> {
+        Title(TextComponent<'a>),
+        Subtitle(TextComponent<'a>),
+        Actionbar(TextComponent<'a>),
+        /// Sets the fade in, stay and fade out times, in ticks
+        Times {fade_in: u64, stay: u64, fade_out: u64},
+        Clear,
+        Reset
+    }
+
+    /// The `title` command.  
+    /// Syntax: `title <targets> <action>`
+    pub struct Title<'a> {
+        pub targets: Selector<'a>,
+        pub action: TitleAction<'a>
+    }
+    pub fn Title<'a>(targets: Selector<'a>, action: TitleAction<'a>) -> Title<'a> {
+        Title {targets, action}
+    }
+    impl Command for Title<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "title {} ", self.targets).unwrap();
+            match self.action {
+                TitleAction::Title(title) => write!(out, "title {}", title),
+                TitleAction::Subtitle(subtitle) => write!(out, "subtitle {}", subtitle),
+                TitleAction::Actionbar(actionbar) => write!(out, "actionbar {}", actionbar),
+                TitleAction::Times {fade_in, stay, fade_out} => write!(out, "times {} {} {}", fade_in, stay, fade_out),
+                TitleAction::Clear => write!(out, "clear"),
+                TitleAction::Reset => write!(out, "reset")
+            }.unwrap();
+        }
+    }
+
+    /// The `say` command.  
+    /// Syntax: `say <message>`
+    pub struct Say<'a> {
+        pub message: &'a str
+    }
+    pub fn Say(message: &str) -> Say<'_> {
+        Say {message}
+    }
+    impl Command for Say<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "say {}", self.message).unwrap();
+        }
+    }
+
+    /// The `msg` command.  
+    /// Syntax: `msg <targets> <message>`
+    pub struct Msg<'a> {
+        pub targets: Selector<'a>,
+        pub message: &'a str
+    }
+    pub fn Msg<'a>(targets: Selector<'a>, message: &'a str) -> Msg<'a> {
+        Msg {targets, message}
+    }
+    impl Command for Msg<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "msg {} {}", self.targets, self.message).unwrap();
+        }
+    }
+
+    /// The `scoreboard objectives add` command.
+    /// Prefer [`Datapack::objective`](crate::datapack::Datapack::objective), which runs this when the pack is loaded.  
+    /// Syntax: `scoreboard objectives add <objective> <criterion> [<display_name>]`
+    pub struct ObjectiveAdd<'a> {
+        pub objective: &'a Objective,
+        pub criterion: &'a str,
+        pub display_name: Option<TextComponent<'a>>
+    }
+    pub fn ObjectiveAdd<'a>(objective: &'a Objective, criterion: &'a str) -> ObjectiveAdd<'a> {
+        ObjectiveAdd {objective, criterion, display_name: None}
+    }
+    impl Command for ObjectiveAdd<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "scoreboard objectives add {} {}", self.objective, self.criterion).unwrap();
+            if let Some(display_name) = self.display_name {write!(out, " {}", display_name).unwrap();}
+        }
+    }
+
+    /// The `scoreboard objectives remove` command.  
+    /// Syntax: `scoreboard objectives remove <objective>`
+    pub struct ObjectiveRemove<'a> {
+        pub objective: &'a Objective
+    }
+    pub fn ObjectiveRemove(objective: &Objective) -> ObjectiveRemove<'_> {
+        ObjectiveRemove {objective}
+    }
+    impl Command for ObjectiveRemove<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "scoreboard objectives remove {}", self.objective).unwrap();
+        }
+    }
+
+    /// A display slot for objectives
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum DisplaySlot {
+        List,
+        Sidebar,
+        BelowName,
+        /// The sidebar shown to members of teams with this color. Hex colors aren't allowed.
+        SidebarTeam(TextColor)
+    }
+    impl Display for DisplaySlot {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::List => write!(f, "list"),
+                Self::Sidebar => write!(f, "sidebar"),
+                Self::BelowName => write!(f, "belowName"),
+                Self::SidebarTeam(color) => write!(f, "sidebar.team.{}", color)
+            }
+        }
+    }
+
+    /// The `scoreboard objectives setdisplay` command. If `objective` is `None`, the slot is cleared.  
+    /// Syntax: `scoreboard objectives setdisplay <slot> [<objective>]`
+    pub struct ObjectiveSetdisplay<'a> {
+        pub slot: DisplaySlot,
+        pub objective: Option<&'a Objective>
+    }
+    pub fn ObjectiveSetdisplay(slot: DisplaySlot, objective: &Objective) -> ObjectiveSetdisplay<'_> {
+        ObjectiveSetdisplay {slot, objective: Some(objective)}
+    }
+    impl Command for ObjectiveSetdisplay<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "scoreboard objectives setdisplay {}", self.slot).unwrap();
+            if let Some(objective) = self.objective {write!(out, " {}", objective).unwrap();}
+        }
+    }
+
+    /// How an objective is rendered
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum RenderType {
+        Integer,
+        Hearts
+    }
+    impl Display for RenderType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", match self {
+                Self::Integer => "integer",
+                Self::Hearts => "hearts"
+            })
+        }
+    }
+
+    /// A change made by [`ObjectiveModify`]
+    pub enum ObjectiveModification<'a> {
+        DisplayName(Box<TextComponent<'a>>),
+        RenderType(RenderType)
+    }
+
+    /// The `scoreboard objectives modify` command.  
+    /// Syntax: `scoreboard objectives modify <objective> (displayname <name>|rendertype <type>)`
+    pub struct ObjectiveModify<'a> {
+        pub objective: &'a Objective,
+        pub modification: ObjectiveModification<'a>
+    }
+    pub fn ObjectiveModify<'a>(objective: &'a Objective, modification: ObjectiveModification<'a>) -> ObjectiveModify<'a> {
+        ObjectiveModify {objective, modification}
+    }
+    impl Command for ObjectiveModify<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "scoreboard objectives modify {} ", self.objective).unwrap();
+            match self.modification {
+                ObjectiveModification::DisplayName(name) => write!(out, "displayname {}", name),
+                ObjectiveModification::RenderType(render_type) => write!(out, "rendertype {}", render_type)
+            }.unwrap();
+        }
+    }
+
+    /// The `scoreboard players get` command. Its result is the score, so it's useful with `execute store`.  
+    /// Syntax: `scoreboard players get <target> <objective>`
+    pub struct ScoreGet<'a> {
+        pub target: ScoreHolder<'a>,
+        pub objective: &'a str
+    }
+    pub fn ScoreGet<'a>(target: ScoreHolder<'a>, objective: &'a str) -> ScoreGet<'a> {
+        ScoreGet {target, objective}
+    }
+    impl Command for ScoreGet<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "scoreboard players get {} {}", self.target, self.objective).unwrap();
+        }
+    }
+
+    /// The arguments passed to a function with macro lines
+    pub enum MacroArguments<'a> {
+        /// An nbt compound, e.g `{name:"foo"}`
+        Compound(BTreeMap<String, Nbt>),
+        /// The compound at a path in a source of nbt data. If the path is `None`, the whole source is used.
+        With(DataTarget<'a>, Option<NbtPath>)
+    }
+    impl Display for MacroArguments<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Compound(compound) => write_compound(f, compound),
+                Self::With(source, None) => write!(f, "with {}", source),
+                Self::With(source, Some(path)) => write!(f, "with {} {}", source, path)
+            }
+        }
+    }
+
+    /// The `function` command. If `tag` is true, every function in the tag is run.  
+    /// Syntax: `function <function> [<arguments>|with (block <pos>|entity <target>|storage <id>) [<path>]]`
+    pub struct FunctionCall<'a> {
+        pub function: Identifier<'a, 'a>,
+        pub tag: bool,
+        pub arguments: Option<MacroArguments<'a>>
+    }
+    pub fn FunctionCall<'a>(function: Identifier<'a, 'a>) -> FunctionCall<'a> {
+        FunctionCall {function, tag: false, arguments: None}
+    }
+    impl Command for FunctionCall<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "function {}", function_reference(&self.function, self.tag)).unwrap();
+            if let Some(arguments) = self.arguments {write!(out, " {}", arguments).unwrap();}
+        }
+        fn references(&self) -> Vec<String> {
+            vec![function_reference(&self.function, self.tag)]
+        }
+    }
+
+    /// Whether [`Schedule`] adds to the existing schedule of a function, or replaces it
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum ScheduleMode {
+        Append, Replace
+    }
+    impl Display for ScheduleMode {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", match self {
+                Self::Append => "append",
+                Self::Replace => "replace"
+            })
+        }
+    }
+
+    /// The `schedule function` command. If `tag` is true, every function in the tag is scheduled.  
+    /// Syntax: `schedule function <function> <ticks>t [append|replace]`
+    pub struct Schedule<'a> {
+        pub function: Identifier<'a, 'a>,
+        pub tag: bool,
+        pub ticks: u64,
+        pub mode: Option<ScheduleMode>
+    }
+    pub fn Schedule<'a>(function: Identifier<'a, 'a>, ticks: u64) -> Schedule<'a> {
+        Schedule {function, tag: false, ticks, mode: None}
+    }
+    impl Command for Schedule<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "schedule function {} {}t", function_reference(&self.function, self.tag), self.ticks).unwrap();
+            if let Some(mode) = self.mode {write!(out, " {}", mode).unwrap();}
+        }
+        fn references(&self) -> Vec<String> {
+            vec![function_reference(&self.function, self.tag)]
+        }
+    }
+
+    /// The `schedule clear` command.  
+    /// Syntax: `schedule clear <function>`
+    pub struct ScheduleClear<'a> {
+        pub function: Identifier<'a, 'a>,
+        pub tag: bool
+    }
+    pub fn ScheduleClear<'a>(function: Identifier<'a, 'a>) -> ScheduleClear<'a> {
+        ScheduleClear {function, tag: false}
+    }
+    impl Command for ScheduleClear<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "schedule clear {}", function_reference(&self.function, self.tag)).unwrap();
+        }
+        fn references(&self) -> Vec<String> {
+            vec![function_reference(&self.function, self.tag)]
+        }
+    }
+
+    /// The `return` command, which stops the function and sets its result.  
+    /// Syntax: `return <value>`
+    pub struct Return {
+        pub value: i64
+    }
+    pub fn Return(value: i64) -> Return {
+        Return {value}
+    }
+    impl Command for Return {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "return {}", self.value).unwrap();
+        }
+    }
+
+    /// The `return run` command, which stops the function, using the result of another command.  
+    /// Syntax: `return run <command>`
+    pub struct ReturnRun<C: Command> {
+        pub command: C
+    }
+    pub fn ReturnRun<C: Command>(command: C) -> ReturnRun<C> {
+        ReturnRun {command}
+    }
+    impl<C: Command> Command for ReturnRun<C> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "return run ").unwrap();
+            self.command.output(out);
+        }
+        fn references(&self) -> Vec<String> {
+            self.command.references()
+        }
+    }
+
+    /// An anchor, used by `execute anchored` and `execute facing entity`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum Anchor {
+        Eyes, Feet
+    }
+    impl Display for Anchor {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", match self {
+                Self::Eyes => "eyes",
+                Self::Feet => "feet"
+            })
+        }
+    }
+
+    /// A set of axes, used by `execute align`, e.g `xz`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Swizzle {
+        x: bool,
+        y: bool,
+        z: bool
+    }
+    impl Swizzle {
+        /// Create a swizzle from the axes it contains. Panics if it contains none of them.
+        pub fn new(x: bool, y: bool, z: bool) -> Self {
+            assert!(x || y || z, "a swizzle must contain at least one axis");
+            Self {x, y, z}
+        }
+    }
+    impl Display for Swizzle {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.x {write!(f, "x")?;}
+            if self.y {write!(f, "y")?;}
+            if self.z {write!(f, "z")?;}
+            Ok(())
+        }
+    }
+
+    /// The numeric type used to store a value in nbt
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum NumericType {
+        Byte, Short, Int, Long, Float, Double
+    }
+    impl Display for NumericType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use NumericType::*;
+            write!(f, "{}", match self {
+                Byte => "byte",
+                Short => "short",
+                Int => "int",
+                Long => "long",
+                Float => "float",
+                Double => "double"
+            })
+        }
+    }
+
+    /// The `data get` command. If `path` is `None`, the whole target is read.
+    /// Otherwise, the value at the path is read, and multiplied by the scale if it's set.  
+    /// Syntax: `data get <target> [<path> [<scale>]]`
+    pub struct DataGet<'a> {
+        pub target: DataTarget<'a>,
+        pub path: Option<(NbtPath, Option<f64>)>
+    }
+    pub fn DataGet(target: DataTarget<'_>) -> DataGet<'_> {
+        DataGet {target, path: None}
+    }
+    impl Command for DataGet<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "data get {}", self.target).unwrap();
+            if let Some((path, scale)) = self.path {
+                write!(out, " {}", path).unwrap();
+                if let Some(scale) = scale {write!(out, " {}", scale).unwrap();}
+            }
+        }
+    }
+
+    /// The `data merge` command.  
+    /// Syntax: `data merge <target> <nbt>`
+    pub struct DataMerge<'a> {
+        pub target: DataTarget<'a>,
+        pub nbt: Nbt
+    }
+    pub fn DataMerge(target: DataTarget<'_>, nbt: Nbt) -> DataMerge<'_> {
+        DataMerge {target, nbt}
+    }
+    impl Command for DataMerge<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "data merge {} {}", self.target, self.nbt).unwrap();
+        }
+    }
+
+    /// The `data remove` command.  
+    /// Syntax: `data remove <target> <path>`
+    pub struct DataRemove<'a> {
+        pub target: DataTarget<'a>,
+        pub path: NbtPath
+    }
+    pub fn DataRemove(target: DataTarget<'_>, path: NbtPath) -> DataRemove<'_> {
+        DataRemove {target, path}
+    }
+    impl Command for DataRemove<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "data remove {} {}", self.target, self.path).unwrap();
+        }
+    }
+
+    /// How [`DataModify`] changes the nbt at its path
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum DataModifyOperation {
+        Set,
+        /// Adds to the end of a list
+        Append,
+        /// Adds to the start of a list
+        Prepend,
+        /// Inserts into a list at an index
+        Insert(i32),
+        /// Merges into a compound
+        Merge
+    }
+    impl Display for DataModifyOperation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Set => write!(f, "set"),
+                Self::Append => write!(f, "append"),
+                Self::Prepend => write!(f, "prepend"),
+                Self::Insert(index) => write!(f, "insert {}", index),
+                Self::Merge => write!(f, "merge")
+            }
+        }
+    }
+
+    /// Where [`DataModify`] gets its value from
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum DataModifySource<'a> {
+        /// Syntax: `from <source> [<path>]`
+        From(DataTarget<'a>, Option<NbtPath>),
+        /// Syntax: `value <nbt>`
+        Value(Nbt),
+        /// Part of a string, read from `source` at an optional path.  
+        /// Syntax: `string <source> [<path> [<start> [<end>]]]`
+        String {source: DataTarget<'a>, slice: Option<StringSlice>}
+    }
+    impl Display for DataModifySource<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::From(source, path) => {
+                    write!(f, "from {}", source)?;
+                    if let Some(path) = path {write!(f, " {}", path)?;}
+                }
+                Self::Value(nbt) => write!(f, "value {}", nbt)?,
+                Self::String {source, slice} => {
+                    write!(f, "string {}", source)?;
+                    if let Some(slice) = slice {write!(f, " {}", slice)?;}
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// The string at `path` used by [`DataModifySource::String`]. The range is the index of the first character,
+    /// optionally followed by the index to stop before. Negative indices count from the end.  
+    /// Syntax: `<path> [<start> [<end>]]`
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct StringSlice {
+        pub path: NbtPath,
+        pub range: Option<(i32, Option<i32>)>
+    }
+    impl Display for StringSlice {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.path)?;
+            if let Some((start, end)) = self.range {
+                write!(f, " {}", start)?;
+                if let Some(end) = end {write!(f, " {}", end)?;}
+            }
+            Ok(())
+        }
+    }
+
+    /// The `data modify` command.  
+    /// Syntax: `data modify <target> <path> <operation> <source>`
+    pub struct DataModify<'a> {
+        pub target: DataTarget<'a>,
+        pub path: NbtPath,
+        pub operation: DataModifyOperation,
+        pub source: DataModifySource<'a>
+    }
+    pub fn DataModify<'a>(target: DataTarget<'a>, path: NbtPath, operation: DataModifyOperation, source: DataModifySource<'a>) -> DataModify<'a> {
+        DataModify {target, path, operation, source}
+    }
+    impl Command for DataModify<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "data modify {} {} {} {}", self.target, self.path, self.operation, self.source).unwrap();
+        }
+    }
+
+    /// A comparison between two scores, used by `execute if score`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum ScoreComparison {
+        Less, LessEqual, Equal, GreaterEqual, Greater
+    }
+    impl Display for ScoreComparison {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use ScoreComparison::*;
+            write!(f, "{}", match self {
+                Less => "<",
+                LessEqual => "<=",
+                Equal => "=",
+                GreaterEqual => ">=",
+                Greater => ">"
+            })
+        }
+    }
+
+    /// Which blocks are compared by `execute if blocks`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum BlocksMode {
+        All, Masked
+    }
+
+    /// A condition checked by `execute if` or `execute unless`
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum ExecuteCondition<'a> {
+        /// Syntax: `block <pos> <block>`
+        Block(Coordinates, Block),
+        /// Syntax: `blocks <start> <end> <destination> all|masked`
+        Blocks {start: Coordinates, end: Coordinates, destination: Coordinates, mode: BlocksMode},
+        /// Syntax: `entity <target>`
+        Entity(Box<Selector<'a>>),
+        /// Syntax: `score <target> <objective> <comparison> <source> <source_objective>`
+        Score {target: ScoreHolder<'a>, objective: &'a str, comparison: ScoreComparison, source: ScoreHolder<'a>, source_objective: &'a str},
+        /// Syntax: `score <target> <objective> matches <range>`
+        ScoreMatches {target: ScoreHolder<'a>, objective: &'a str, range: NumberRange<i64>},
+        /// Syntax: `predicate <predicate>`
+        Predicate(Identifier<'a, 'a>),
+        /// Syntax: `data <source> <path>`
+        Data(DataTarget<'a>, NbtPath)
+    }
+    impl Display for ExecuteCondition<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use ExecuteCondition::*;
+            match self {
+                Block(location, block) => write!(f, "block {} {}", location, block),
+                Blocks {start, end, destination, mode} => write!(f, "blocks {} {} {} {}", start, end, destination, match mode {
+                    BlocksMode::All => "all",
+                    BlocksMode::Masked => "masked"
+                }),
+                Entity(target) => write!(f, "entity {}", target),
+                Score {target, objective, comparison, source, source_objective} =>
+                    write!(f, "score {} {} {} {} {}", target, objective, comparison, source, source_objective),
+                ScoreMatches {target, objective, range} => write!(f, "score {} {} matches {}", target, objective, range),
+                Predicate(id) => write!(f, "predicate {}", id),
+                Data(source, path) => write!(f, "data {} {}", source, path)
+            }
+        }
+    }
+
+    /// An [`ExecuteCondition`] along with whether it should pass (`if`) or fail (`unless`).
+    /// Use `!` to invert it.  
+    /// Syntax: `if|unless <check>`
+    #[derive(Debug, PartialEq, Clone)]
+    pub struct Condition<'a> {
+        pub check: ExecuteCondition<'a>,
+        pub positive: bool
+    }
+    impl Not for Condition<'_> {
+        type Output = Self;
+
+        fn not(self) -> Self::Output {
+            Self {positive: !self.positive, ..self}
+        }
+    }
+    impl Display for Condition<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} {}", if self.positive {"if"} else {"unless"}, self.check)
+        }
+    }
+
+    /// Whether `execute store` stores the result or the success of the command
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum StoreKind {
+        Result, Success
+    }
+
+    /// A value of a bossbar, used by `execute store`
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub enum BossbarValue {
+        Value, Max
+    }
+
+    /// Where `execute store` stores its value
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum StoreTarget<'a> {
+        /// Syntax: `score <target> <objective>`
+        Score(ScoreHolder<'a>, &'a str),
+        /// Syntax: `storage <target> <path> <type> <scale>`
+        Storage(Identifier<'a, 'a>, NbtPath, NumericType, f64),
+        /// Syntax: `entity <target> <path> <type> <scale>`
+        Entity(Box<Selector<'a>>, NbtPath, NumericType, f64),
+        /// Syntax: `block <pos> <path> <type> <scale>`
+        Block(Coordinates, NbtPath, NumericType, f64),
+        /// Syntax: `bossbar <id> value|max`
+        Bossbar(Identifier<'a, 'a>, BossbarValue)
+    }
+    impl Display for StoreTarget<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Score(target, objective) => write!(f, "score {} {}", target, objective),
+                Self::Storage(id, path, ty, scale) => write!(f, "storage {} {} {} {}", id, path, ty, scale),
+                Self::Entity(target, path, ty, scale) => write!(f, "entity {} {} {} {}", target, path, ty, scale),
+                Self::Block(location, path, ty, scale) => write!(f, "block {} {} {} {}", location, path, ty, scale),
+                Self::Bossbar(id, value) => write!(f, "bossbar {} {}", id, match value {
+                    BossbarValue::Value => "value",
+                    BossbarValue::Max => "max"
+                })
+            }
+        }
+    }
+
+    /// A single subcommand of `execute`
+    #[derive(Debug, PartialEq, Clone)]
+    pub enum ExecuteSubcommand<'a> {
+        /// Syntax: `as <targets>`
+        As(Box<Selector<'a>>),
+        /// Syntax: `at <targets>`
+        At(Box<Selector<'a>>),
+        /// Syntax: `positioned <pos>`
+        Positioned(Coordinates),
+        /// Syntax: `positioned as <targets>`
+        PositionedAs(Box<Selector<'a>>),
+        /// Syntax: `rotated <y_rot> <x_rot>`
+        Rotated(Coordinate, Coordinate),
+        /// Syntax: `rotated as <targets>`
+        RotatedAs(Box<Selector<'a>>),
+        /// Syntax: `facing <pos>`
+        Facing(Coordinates),
+        /// Syntax: `facing entity <targets> <anchor>`
+        FacingEntity(Box<Selector<'a>>, Anchor),
+        /// Syntax: `align <axes>`
+        Align(Swizzle),
+        /// Syntax: `anchored <anchor>`
+        Anchored(Anchor),
+        /// Syntax: `in <dimension>`
+        In(Identifier<'a, 'a>),
+        /// Syntax: `if|unless <check>`
+        Condition(Condition<'a>),
+        /// Syntax: `store result|success <target>`
+        Store(StoreKind, StoreTarget<'a>)
+    }
+    impl Display for ExecuteSubcommand<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use ExecuteSubcommand::*;
+            match self {
+                As(targets) => write!(f, "as {}", targets),
+                At(targets) => write!(f, "at {}", targets),
+                Positioned(location) => write!(f, "positioned {}", location),
+                PositionedAs(targets) => write!(f, "positioned as {}", targets),
+                Rotated(y_rot, x_rot) => write!(f, "rotated {} {}", y_rot, x_rot),
+                RotatedAs(targets) => write!(f, "rotated as {}", targets),
+                Facing(location) => write!(f, "facing {}", location),
+                FacingEntity(targets, anchor) => write!(f, "facing entity {} {}", targets, anchor),
+                Align(axes) => write!(f, "align {}", axes),
+                Anchored(anchor) => write!(f, "anchored {}", anchor),
+                In(dimension) => write!(f, "in {}", dimension),
+                Condition(condition) => write!(f, "{}", condition),
+                Store(StoreKind::Result, target) => write!(f, "store result {}", target),
+                Store(StoreKind::Success, target) => write!(f, "store success {}", target)
+            }
+        }
+    }
+
+    /// The `execute` command, built from a chain of [`ExecuteSubcommand`]s.  
+    /// Syntax: `execute <subcommands...> [run <command>]`
+    ///
+    /// Each subcommand has a builder method. Use [`Execute::run`] to run a command:
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// Execute().as_(at_a()).at(at_s()).run(Kill()); // execute as @a at @s run kill
+    /// ```
+    #[derive(Debug, PartialEq, Clone, Default)]
+    pub struct Execute<'a> {
+        pub subcommands: Vec<ExecuteSubcommand<'a>>
+    }
+    pub fn Execute<'a>() -> Execute<'a> {
+        Execute {subcommands: Vec::new()}
+    }
+    impl<'a> Execute<'a> {
+        fn then(mut self, subcommand: ExecuteSubcommand<'a>) -> Self {
+            self.subcommands.push(subcommand);
+            self
+        }
+        /// Adds `as <targets>`
+        pub fn as_(self, targets: Selector<'a>) -> Self {
+            self.then(ExecuteSubcommand::As(Box::new(targets)))
+        }
+        /// Adds `at <targets>`
+        pub fn at(self, targets: Selector<'a>) -> Self {
+            self.then(ExecuteSubcommand::At(Box::new(targets)))
+        }
+        /// Adds `positioned <pos>`
+        pub fn positioned(self, location: Coordinates) -> Self {
+            self.then(ExecuteSubcommand::Positioned(location))
+        }
+        /// Adds `positioned as <targets>`
+        pub fn positioned_as(self, targets: Selector<'a>) -> Self {
+            self.then(ExecuteSubcommand::PositionedAs(Box::new(targets)))
+        }
+        /// Adds `rotated <y_rot> <x_rot>`
+        pub fn rotated(self, y_rot: Coordinate, x_rot: Coordinate) -> Self {
+            self.then(ExecuteSubcommand::Rotated(y_rot, x_rot))
+        }
+        /// Adds `rotated as <targets>`
+        pub fn rotated_as(self, targets: Selector<'a>) -> Self {
+            self.then(ExecuteSubcommand::RotatedAs(Box::new(targets)))
+        }
+        /// Adds `facing <pos>`
+        pub fn facing(self, location: Coordinates) -> Self {
+            self.then(ExecuteSubcommand::Facing(location))
+        }
+        /// Adds `facing entity <targets> <anchor>`
+        pub fn facing_entity(self, targets: Selector<'a>, anchor: Anchor) -> Self {
+            self.then(ExecuteSubcommand::FacingEntity(Box::new(targets), anchor))
+        }
+        /// Adds `align <axes>`
+        pub fn align(self, axes: Swizzle) -> Self {
+            self.then(ExecuteSubcommand::Align(axes))
+        }
+        /// Adds `anchored <anchor>`
+        pub fn anchored(self, anchor: Anchor) -> Self {
+            self.then(ExecuteSubcommand::Anchored(anchor))
+        }
+        /// Adds `in <dimension>`
+        pub fn in_(self, dimension: Identifier<'a, 'a>) -> Self {
+            self.then(ExecuteSubcommand::In(dimension))
+        }
+        /// Adds `if <check>`
+        pub fn if_(self, check: ExecuteCondition<'a>) -> Self {
+            self.condition(Condition {check, positive: true})
+        }
+        /// Adds `unless <check>`
+        pub fn unless(self, check: ExecuteCondition<'a>) -> Self {
+            self.condition(Condition {check, positive: false})
+        }
+        /// Adds a [`Condition`], which is either `if` or `unless`
+        pub fn condition(self, condition: Condition<'a>) -> Self {
+            self.then(ExecuteSubcommand::Condition(condition))
+        }
+        /// Adds `store result <target>`
+        pub fn store_result(self, target: StoreTarget<'a>) -> Self {
+            self.then(ExecuteSubcommand::Store(StoreKind::Result, target))
+        }
+        /// Adds `store success <target>`
+        pub fn store_success(self, target: StoreTarget<'a>) -> Self {
+            self.then(ExecuteSubcommand::Store(StoreKind::Success, target))
+        }
+        /// Runs a command, finishing the `execute` command
+        pub fn run<C: Command>(self, command: C) -> ExecuteRun<'a, C> {
+            ExecuteRun {execute: self, command}
+        }
+    }
+    impl Display for Execute<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "execute")?;
+            for subcommand in &self.subcommands {
+                write!(f, " {}", subcommand)?;
+            }
+            Ok(())
+        }
+    }
+    impl Command for Execute<'_> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "{}", self).unwrap();
+        }
+    }
+
+    /// An `execute` command which runs another command, created with [`Execute::run`].  
+    /// Syntax: `execute <subcommands...> run <command>`
+    pub struct ExecuteRun<'a, C: Command> {
+        pub execute: Execute<'a>,
+        pub command: C
+    }
+    impl<C: Command> Command for ExecuteRun<'_, C> {
+        fn output(self, out: &mut impl Write) {
+            write!(out, "{} run ", self.execute).unwrap();
+            self.command.output(out);
+        }
+        fn references(&self) -> Vec<String> {
+            self.command.references()
+        }
+    }
 }
\ No newline at end of file
@@ -5,7 +5,7 @@ Variants are passed to a datapack via [`Datapack::item_modifier`](crate::datapac
 
 use serde::{Serialize, Serializer, ser::SerializeMap};
 
-use crate::{core::{Color, Identifier}, minecraft::*};
+use crate::{core::{Color, Identifier, nbt::{Nbt, NbtPath}, text::TextComponent}, minecraft::*};
 
 
 /// A general context entity
@@ -106,6 +106,13 @@ pub enum NumberProvider<'a, N: Number> {
         score: &'a str,
         /// The scale to multiply the score by
         scale: f64
+    },
+    /// A number read from a storage, `{"type": "storage"}`
+    Storage {
+        /// The storage to read from
+        storage: Identifier<'a, 'a>,
+        /// The path of the number in the storage
+        path: NbtPath
     }
 }
 impl<N: Number> Serialize for NumberProvider<'_, N> {
@@ -136,6 +143,11 @@ impl<N: Number> Serialize for NumberProvider<'_, N> {
                         map.serialize_entry("scale", scale)?;
                     }
                 }
+                Storage {storage, path} => {
+                    map.serialize_entry("type", "storage")?;
+                    map.serialize_entry("storage", storage)?;
+                    map.serialize_entry("path", path)?;
+                }
                 Constant(..) => unreachable!()
             }
             map.end()
@@ -219,11 +231,11 @@ pub enum CopyNbtOperationType {
 
 /// Copies nbt to the item's `tag` tag
 #[derive(Serialize)]
-pub struct CopyNbtOperation<'a> {
+pub struct CopyNbtOperation {
     /// The nbt path to copy from
-    pub source: &'a str,
+    pub source: NbtPath,
     /// The nbt path to copy to, starting at the item's `tag` tag.
-    pub target: &'a str,
+    pub target: NbtPath,
     /// The operation to do
     pub op: CopyNbtOperationType
 }
@@ -313,7 +325,7 @@ pub struct StatusEffect<'a> {
 
 /// An item modifier. Use [`DataPack::item_modifier`](crate::datapack::Datapack::item_modifier)
 #[derive(Serialize)]
-#[serde(tag = "function")]
+#[serde(tag = "function", rename_all = "snake_case")]
 pub enum ItemModifier<'a, 'b> {
     /// Apply a bonus enchantment to the item
     #[serde(serialize_with = "serialize_apply_bonus")]
@@ -331,7 +343,7 @@ pub enum ItemModifier<'a, 'b> {
         /// Specifies the source of the nbt
         source: CopyNbtSource<'a, 'b>,
         /// List of copy operations to do
-        ops: &'a [CopyNbtOperation<'b>]
+        ops: &'a [CopyNbtOperation]
     },
     /// Copies state from dropped block to the item's `BlockStateTag` tag
     CopyState {
@@ -454,8 +466,7 @@ pub enum ItemModifier<'a, 'b> {
     /// Adds lore to the item
     SetLore {
         /// A list of JSON components that make up the lore
-        // TODO: port this to use a proper typed struct
-        lore: &'a [&'b str],
+        lore: &'a [TextComponent<'b>],
         /// The entity to use as `@s` in the lore
         entity: PlayerContextEntity,
         /// Whether to add these lines to the existing lore
@@ -465,14 +476,14 @@ pub enum ItemModifier<'a, 'b> {
     /// Adds display name of the item
     SetName {
         /// A JSON name
-        name: &'a str,
+        name: &'a TextComponent<'b>,
         /// The entity to use as `@s` in the lore
         entity: PlayerContextEntity
     },
     /// Adds nbt data to the item
     SetNbt {
         /// The tag to add
-        tag: &'a str
+        tag: Nbt
     },
     /// Sets the status effects for suspicious stew
     SetStewEffect {
@@ -0,0 +1,161 @@
+/*!
+Contains the [`LootTable`] struct.
+Loot tables are passed to a datapack via [`Datapack::loot_table`](crate::datapack::Datapack::loot_table).
+
+A loot table is made of [`LootPool`]s, each of which rolls some [`LootEntry`]s.
+Conditions are [`Predicate`]s and functions are [`ItemModifier`]s, so anything already built for those can be reused here.
+For example:
+```
+# use copper::prelude::*;
+# use copper::datapack::{loot_table::*, item_modifier::{ItemModifier, NumberProvider}, predicate::Predicate};
+LootTable {
+    ty: Some(LootContextType::Entity),
+    pools: &[LootPool {
+        entries: &[LootEntry::Item {
+            name: Item::Diamond,
+            properties: EntryProperties {
+                functions: Some(&[ItemModifier::SetCount {count: NumberProvider::Constant(2), add: false}]),
+                ..default()
+            }
+        }],
+        conditions: Some(&[Predicate::KilledByPlayer {inverse: false}]),
+        ..default()
+    }],
+    functions: None
+};
+```
+*/
+
+use serde::Serialize;
+
+use crate::{core::Identifier, minecraft::*};
+
+use super::{item_modifier::{ItemModifier, NumberProvider}, predicate::Predicate};
+
+/// The context a loot table is invoked in. This is used to validate the table.
+#[derive(Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum LootContextType {
+    Empty, Chest, Command, Selector, Fishing, Entity, Gift, Barter, AdvancementReward, AdvancementEntity, Generic, Block
+}
+
+/// The item a `dynamic` entry drops
+#[derive(Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DynamicDrop {
+    /// Drops the contents of a container block (e.g a shulker box)
+    Contents,
+    /// Drops the block itself (e.g a banner)
+    #[serde(rename = "self")]
+    This
+}
+
+config_struct! {
+    struct EntryProperties<'a, 'b> where "Properties shared by all singleton loot entries" {
+        weight: i64 where "How often this entry is chosen, relative to the other entries in the pool",
+        quality: i64 where "Modifies the weight based on the killer's luck",
+        conditions: &'a [Predicate<'b, 'b>] where "Conditions which must all pass for this entry to be chosen",
+        functions: &'a [ItemModifier<'b, 'b>] where "Item modifiers applied to the dropped items"
+    }
+}
+
+/// An entry in a [`LootPool`]
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LootEntry<'a, 'b> {
+    /// Drops an item
+    Item {
+        /// The item to drop
+        name: Item,
+        /// The properties of the entry
+        #[serde(flatten)] properties: EntryProperties<'a, 'b>
+    },
+    /// Drops items from an item tag
+    Tag {
+        /// The tag to use
+        name: Identifier<'a, 'b>,
+        /// If true, one item of the tag is chosen (as if each were its own entry).
+        /// Otherwise every item in the tag is dropped.
+        expand: bool,
+        /// The properties of the entry
+        #[serde(flatten)] properties: EntryProperties<'a, 'b>
+    },
+    /// Drops the result of another loot table
+    LootTable {
+        /// The loot table to use
+        name: Identifier<'a, 'b>,
+        /// The properties of the entry
+        #[serde(flatten)] properties: EntryProperties<'a, 'b>
+    },
+    /// Drops block specific contents
+    Dynamic {
+        /// What to drop
+        name: DynamicDrop,
+        /// The properties of the entry
+        #[serde(flatten)] properties: EntryProperties<'a, 'b>
+    },
+    /// Drops nothing
+    Empty {
+        /// The properties of the entry
+        #[serde(flatten)] properties: EntryProperties<'a, 'b>
+    },
+    /// Applies all of its children
+    Group {
+        /// The entries to apply
+        children: &'a [LootEntry<'b, 'b>],
+        /// Conditions which must all pass for the children to be applied
+        #[serde(skip_serializing_if = "Option::is_none")] conditions: Option<&'a [Predicate<'b, 'b>]>
+    },
+    /// Applies the first child whose conditions pass
+    Alternatives {
+        /// The entries to try
+        children: &'a [LootEntry<'b, 'b>],
+        /// Conditions which must all pass for the children to be applied
+        #[serde(skip_serializing_if = "Option::is_none")] conditions: Option<&'a [Predicate<'b, 'b>]>
+    },
+    /// Applies its children in order, until one of their conditions fails
+    Sequence {
+        /// The entries to apply
+        children: &'a [LootEntry<'b, 'b>],
+        /// Conditions which must all pass for the children to be applied
+        #[serde(skip_serializing_if = "Option::is_none")] conditions: Option<&'a [Predicate<'b, 'b>]>
+    }
+}
+
+/// A pool of entries in a [`LootTable`]. Implements default with a single roll and no entries.
+#[derive(Serialize)]
+pub struct LootPool<'a, 'b> {
+    /// The number of times to roll the pool
+    pub rolls: NumberProvider<'b, i64>,
+    /// The number of extra rolls, multiplied by the player's luck
+    #[serde(skip_serializing_if = "Option::is_none")] pub bonus_rolls: Option<NumberProvider<'b, f64>>,
+    /// The entries to choose from
+    pub entries: &'a [LootEntry<'b, 'b>],
+    /// Conditions which must all pass for the pool to be used
+    #[serde(skip_serializing_if = "Option::is_none")] pub conditions: Option<&'a [Predicate<'b, 'b>]>,
+    /// Item modifiers applied to every item generated by the pool
+    #[serde(skip_serializing_if = "Option::is_none")] pub functions: Option<&'a [ItemModifier<'b, 'b>]>
+}
+impl Default for LootPool<'_, '_> {
+    fn default() -> Self {
+        Self {
+            rolls: NumberProvider::Constant(1),
+            bonus_rolls: None,
+            entries: &[],
+            conditions: None,
+            functions: None
+        }
+    }
+}
+
+/// A loot table. Use [`Datapack::loot_table`](crate::datapack::Datapack::loot_table).
+#[derive(Serialize)]
+pub struct LootTable<'a, 'b> {
+    /// The context the loot table is used in
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")] pub ty: Option<LootContextType>,
+    /// The pools to roll
+    pub pools: &'a [LootPool<'b, 'b>],
+    /// Item modifiers applied to every item generated by the table
+    #[serde(skip_serializing_if = "Option::is_none")] pub functions: Option<&'a [ItemModifier<'b, 'b>]>
+}
@@ -1,20 +1,112 @@
-use std::{fs::File, path::{Path, PathBuf}};
-use std::fs;
+use std::{cell::RefCell, collections::BTreeSet, fs::File, path::{Path, PathBuf}, rc::Rc};
+use std::{fs, io::Write};
 
+use advancement::Advancement;
 use fs::create_dir_all;
-use function::Function;
+use function::{Command, Function, ObjectiveAdd};
 use item_modifier::ItemModifier;
+use loot_table::LootTable;
 use predicate::Predicate;
+use recipe::Recipe;
+use tag::{FunctionTag, Tag, TagEntry, TagType};
 
-use crate::core::Identifier;
+use crate::{core::{Identifier, OwnedIdentifier, text::TextComponent}, score::{CONST, Objective}};
 
+macro_rules! config_struct {
+    ($(struct $name:ident $(<$($life:lifetime),+>)? where $structdoc:literal {
+        $($({$serializer:literal})? $([$rename:literal])? $field:ident : $ty:ty where $doc:literal),*
+    })+) => {
+        $(
+            #[derive(Default, Serialize)]
+            #[doc = $structdoc]
+            pub struct $name $(<$($life),+>)? {
+                $(
+                    #[doc = $doc]
+                    #[serde(skip_serializing_if = "Option::is_none" $(, serialize_with = $serializer)? $(, rename = $rename)?)]
+                    pub $field : Option<$ty>
+                ),*
+            }
+        )+
+    };
+}
+
+pub mod advancement;
 pub mod function;
 pub mod item_modifier;
+pub mod loot_table;
 pub mod predicate;
+pub mod recipe;
+pub mod tag;
 
 /// A datapack. This struct creates and handles a datapack.
+///
+/// The pack is finalized by [`Datapack::finish`], which writes the files collected while building it
+/// (like the `minecraft:load` and `minecraft:tick` function tags), and checks that every function it refers to exists.
+/// If the pack is dropped without being finished, it is still finalized, but references to missing functions are only printed as a warning.
 pub struct Datapack {
-    data: PathBuf
+    data: PathBuf,
+    state: Rc<RefCell<PackState>>,
+    finished: bool
+}
+
+/// The error returned by [`Datapack::finish`] when the pack refers to functions or function tags which don't exist
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DanglingReferences {
+    /// Each missing function or function tag (prefixed with `#`), along with where it was referenced
+    pub references: Vec<(String, String)>
+}
+impl std::fmt::Display for DanglingReferences {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("the pack refers to functions which don't exist: ")?;
+        for (i, (reference, from)) in self.references.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}` (referenced in `{}`)", reference, from)?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for DanglingReferences {}
+
+/// State shared between a [`Datapack`] and the functions and scores created from it
+#[derive(Default)]
+pub (crate) struct PackState {
+    load: Vec<OwnedIdentifier>,
+    tick: Vec<OwnedIdentifier>,
+    objectives: Vec<Objective>,
+    setup: Vec<u8>,
+    constants: BTreeSet<i64>,
+    /// Every function and function tag (prefixed with `#`) which is known to exist
+    pub (crate) functions: BTreeSet<String>,
+    /// References to functions made by commands, in the form `(reference, function containing the command)`
+    references: Vec<(String, String)>
+}
+impl PackState {
+    fn objective(&mut self, name: &str, criterion: &str, display_name: Option<TextComponent<'_>>) -> Objective {
+        assert!(name.chars().count() <= 16, "objective name `{}` is longer than 16 characters", name);
+        let objective = Objective::new(name);
+        assert!(!self.objectives.contains(&objective), "objective `{}` was already created", name);
+        ObjectiveAdd {display_name, ..ObjectiveAdd(&objective, criterion)}.output(&mut self.setup);
+        self.setup.push(b'\n');
+        self.objectives.push(objective.clone());
+        objective
+    }
+    /// Adds one of copper's own dummy objectives, like `copper.temp`, if it hasn't been added yet
+    pub (crate) fn internal_objective(&mut self, name: &str) {
+        if !self.objectives.iter().any(|objective| objective.name() == name) {
+            self.objective(name, "dummy", None);
+        }
+    }
+    /// Records a reference to a function or function tag, which is checked when the pack is finalized
+    pub (crate) fn reference(&mut self, reference: String, from: &str) {
+        self.references.push((reference, from.to_string()));
+    }
+    /// Adds a constant to the pool, which is set in the `copper.const` objective when the pack is loaded
+    pub (crate) fn constant(&mut self, n: i64) {
+        self.internal_objective(CONST);
+        self.constants.insert(n);
+    }
 }
 impl Datapack {
     /// Create a [`Datapack`] from a [`Path`]
@@ -31,12 +123,36 @@ r#"{
 "#
         ).unwrap();
         Self {
-            data: out.as_ref().join("data")
+            data: out.as_ref().join("data"),
+            state: Rc::default(),
+            finished: false
         }
     }
     /// Create a function file
     pub fn function(&self, location: Identifier<'_, '_>) -> Function {
-        Function::new(&self.data, location)
+        Function::new(&self.data, location, self.state.clone())
+    }
+    /// Create a function file which is run when the pack is loaded, by adding it to the `minecraft:load` tag
+    pub fn load_function(&self, location: Identifier<'_, '_>) -> Function {
+        self.state.borrow_mut().load.push(location.into());
+        self.function(location)
+    }
+    /// Create a function file which is run every tick, by adding it to the `minecraft:tick` tag
+    pub fn tick_function(&self, location: Identifier<'_, '_>) -> Function {
+        self.state.borrow_mut().tick.push(location.into());
+        self.function(location)
+    }
+    /// Create a scoreboard objective, which is added by the generated `copper:load` function when the pack is loaded.
+    /// Panics if an objective with the same name was already created, or if the name is longer than 16 characters.
+    /// ```
+    /// # use copper::prelude::*;
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// let kills = pack.objective("kills", "playerKillCount", Some("Kills".into()));
+    /// let mut func = pack.function(id!(test:func));
+    /// let score = func.score("@s", &kills);
+    /// ```
+    pub fn objective(&self, name: &str, criterion: &str, display_name: Option<TextComponent<'_>>) -> Objective {
+        self.state.borrow_mut().objective(name, criterion, display_name)
     }
     /// Create an item modifier
     pub fn item_modifier(&self, location: Identifier<'_, '_>, item_modifier: ItemModifier<'_, '_>) {
@@ -50,4 +166,123 @@ r#"{
         let out = File::create(location.join(&self.data, "predicates", "json")).unwrap();
         serde_json::to_writer(out, &predicate).unwrap();
     }
-}
\ No newline at end of file
+    /// Create a loot table
+    pub fn loot_table(&self, location: Identifier<'_, '_>, loot_table: LootTable<'_, '_>) {
+        let path = location.join(&self.data, "loot_tables", "json");
+        let _ = create_dir_all(path.parent().unwrap());
+        let out = File::create(path).unwrap();
+        serde_json::to_writer(out, &loot_table).unwrap();
+    }
+    /// Create an advancement
+    pub fn advancement(&self, location: Identifier<'_, '_>, advancement: Advancement<'_, '_>) {
+        if let Some(function) = advancement.rewards.as_ref().and_then(|rewards| rewards.function.as_ref()) {
+            self.state.borrow_mut().reference(function.to_string(), &format!("advancement {}", location));
+        }
+        let path = location.join(&self.data, "advancements", "json");
+        let _ = create_dir_all(path.parent().unwrap());
+        let out = File::create(path).unwrap();
+        serde_json::to_writer(out, &advancement).unwrap();
+    }
+    /// Create a recipe
+    pub fn recipe(&self, location: Identifier<'_, '_>, recipe: Recipe<'_, '_>) {
+        let path = location.join(&self.data, "recipes", "json");
+        let _ = create_dir_all(path.parent().unwrap());
+        let out = File::create(path).unwrap();
+        serde_json::to_writer(out, &recipe).unwrap();
+    }
+    /// Create a tag. The folder it is written to depends on `T`, see [`TagType`].
+    pub fn tag<T: TagType>(&self, location: Identifier<'_, '_>, tag: Tag<'_, '_, T>) {
+        if T::FUNCTIONS {
+            let mut state = self.state.borrow_mut();
+            let from = format!("#{}", location);
+            for entry in tag.values {
+                let reference = match entry {
+                    TagEntry::Value(value) => value.function(),
+                    TagEntry::Id {id, required: true} => Some(id.to_string()),
+                    TagEntry::Tag {tag, required: true} => Some(format!("#{}", tag)),
+                    // Entries which aren't required may be missing
+                    _ => None
+                };
+                if let Some(reference) = reference {
+                    state.reference(reference, &from);
+                }
+            }
+            state.functions.insert(from);
+        }
+        let path = location.join(&self.data, &format!("tags/{}", T::FOLDER), "json");
+        let _ = create_dir_all(path.parent().unwrap());
+        let out = File::create(path).unwrap();
+        serde_json::to_writer(out, &tag).unwrap();
+    }
+}
+impl Datapack {
+    /// Declare a function, or a function tag if `tag` is true, which exists outside of this pack (e.g in another datapack).
+    /// Commands like [`FunctionCall`](function::FunctionCall), function tags and advancement rewards may only refer to functions
+    /// created by this pack or declared here; any other reference makes [`Datapack::finish`] fail.
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// pack.external_function(id!(other_pack:setup), false);
+    /// pack.function(id!(test:func)).run(FunctionCall(id!(other_pack:setup)));
+    /// ```
+    pub fn external_function(&self, location: Identifier<'_, '_>, tag: bool) {
+        self.state.borrow_mut().functions.insert(format!("{}{}", if tag {"#"} else {""}, location));
+    }
+    fn function_tag(&self, name: &str, functions: &[OwnedIdentifier]) {
+        if functions.is_empty() {
+            return;
+        }
+        let folders: Vec<_> = functions.iter().map(OwnedIdentifier::folders).collect();
+        let values: Vec<_> = functions.iter().zip(&folders)
+            .map(|(function, folders)| TagEntry::Value(FunctionTag(function.identifier(folders))))
+            .collect();
+        self.tag(Identifier::new("minecraft", &[name]), Tag {replace: false, values: &values});
+    }
+}
+impl Datapack {
+    /// Finalize the pack, writing the files collected while building it.
+    /// Fails if anything in the pack refers to a function which doesn't exist, see [`Datapack::external_function`].
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// let pack = Datapack::new(std::env::temp_dir().join("copper_doc_finish"));
+    /// pack.function(id!(test:func)).run(FunctionCall(id!(test:missing)));
+    /// assert_eq!(pack.finish().unwrap_err().to_string(), "the pack refers to functions which don't exist: `test:missing` (referenced in `test:func`)");
+    /// ```
+    pub fn finish(mut self) -> Result<(), DanglingReferences> {
+        self.finished = true;
+        self.finalize()
+    }
+    fn finalize(&self) -> Result<(), DanglingReferences> {
+        let (load, tick) = {
+            let mut state = self.state.borrow_mut();
+            for n in std::mem::take(&mut state.constants) {
+                writeln!(state.setup, "scoreboard players set #{} {} {}", n, CONST, n).unwrap();
+            }
+            if !state.setup.is_empty() {
+                let path = Identifier::new("copper", &["load"]).join(&self.data, "functions", "mcfunction");
+                let _ = create_dir_all(path.parent().unwrap());
+                fs::write(path, &state.setup).unwrap();
+                state.load.insert(0, Identifier::new("copper", &["load"]).into());
+                state.functions.insert(String::from("copper:load"));
+            }
+            (std::mem::take(&mut state.load), std::mem::take(&mut state.tick))
+        };
+        self.function_tag("load", &load);
+        self.function_tag("tick", &tick);
+        let state = self.state.borrow();
+        let references: Vec<_> = state.references.iter()
+            .filter(|(reference, _)| !state.functions.contains(reference))
+            .cloned()
+            .collect();
+        if references.is_empty() {Ok(())} else {Err(DanglingReferences {references})}
+    }
+}
+impl Drop for Datapack {
+    fn drop(&mut self) {
+        if !self.finished {
+            if let Err(error) = self.finalize() {
+                eprintln!("warning: {}", error);
+            }
+        }
+    }
+}
@@ -28,7 +28,7 @@ EntityPredicate {
 ```
 */
 use serde::{Serialize, Serializer, ser::SerializeMap};
-use crate::{core::{GameMode, Identifier, TupleMapSerializer}, minecraft::*};
+use crate::{core::{GameMode, Identifier, TupleMapSerializer, nbt::Nbt}, minecraft::*};
 use crate::core::serialize_tuple_map;
 
 use super::item_modifier::{Number, NumberProvider, PlayerContextEntity};
@@ -52,24 +52,6 @@ pub enum OptionalRange<N: Number> {
     Range(Range<N>)
 }
 
-macro_rules! config_struct {
-    ($(struct $name:ident $(<$($life:lifetime),+>)? where $structdoc:literal {
-        $($({$serializer:literal})? $([$rename:literal])? $field:ident : $ty:ty where $doc:literal),*
-    })+) => {
-        $(
-            #[derive(Default, Serialize)]
-            #[doc = $structdoc]
-            pub struct $name $(<$($life),+>)? {
-                $(
-                    #[doc = $doc]
-                    #[serde(skip_serializing_if = "Option::is_none" $(, serialize_with = $serializer)? $(, rename = $rename)?)]
-                    pub $field : Option<$ty>
-                ),*
-            }
-        )+
-    };
-}
-
 config_struct! {
     struct DamagePredicate<'a, 'b> where "A predicate for checking damage sources" {
         bypasses_armor: bool where "Checks if the damage bypassed the armor of the player (e.g suffocation)",
@@ -125,7 +107,7 @@ config_struct! {
         equipment: EquipmentPredicate<'a, 'b> where "Equipment to check on the entity",
         flags: EntityFlags where "Predicate flags to be checked",
         location: LocationPredicate<'a, 'b> where "Checks the entity's location",
-        nbt: &'a str where "Checks the entity's nbt",
+        nbt: Nbt where "Checks the entity's nbt",
         player: PlayerPredicate<'a, 'b> where "Player properties to check. Fails if the entity is not a player",
         team: &'a str where "The team the entity belongs to",
         ["type"] ty: Entity where "The entity's type",
@@ -138,14 +120,14 @@ config_struct! {
         enchantments: &'a [EnchantmentPredicate] where "List of enchantments",
         stored_enchantments: &'a [EnchantmentPredicate] where "List of stored enchantments (i.e an enchanted book)",
         item: Item where "An item id",
-        nbt: &'a str where "An nbt string",
+        nbt: Nbt where "Checks the item's nbt",
         potion: Potion where "A potion id",
         tag: Identifier<'a, 'b> where "An item tag"
     }
     struct BlockPredicate<'a, 'b> where "Checks a block" {
         block: Block where "The block to check",
         tag: Identifier<'a, 'b> where "A block tag",
-        nbt: &'a str where "The block nbt",
+        nbt: Nbt where "Checks the block's nbt",
         {"serialize_tuple_map"} state: &'a [(&'b str, BlockstateValue<'b>)] where "Block states to check"
     }
     struct FluidPredicate<'a, 'b> where "Checks a fluid" {
@@ -0,0 +1,252 @@
+/*!
+Contains the [`Recipe`] enum.
+Variants are passed to a datapack via [`Datapack::recipe`](crate::datapack::Datapack::recipe).
+
+Shaped recipes should be created with the [`shaped!`](crate::shaped) macro,
+which checks at compile time that the pattern is at most 3x3, that every character in it has a key and that every key is used:
+```
+# use copper::{shaped, prelude::*, datapack::recipe::*};
+shaped!(["###", " | ", " | "], {
+    '#' => Ingredient::Item(Item::Diamond),
+    '|' => Ingredient::Item(Item::Stick)
+}, RecipeResult {item: Item::DiamondPickaxe, count: 1});
+```
+A pattern with a missing key fails to compile:
+```compile_fail
+# use copper::{shaped, prelude::*, datapack::recipe::*};
+shaped!(["###", " | ", " | "], {
+    '#' => Ingredient::Item(Item::Diamond)
+}, RecipeResult {item: Item::DiamondPickaxe, count: 1});
+```
+So does a key which isn't in the pattern:
+```compile_fail
+# use copper::{shaped, prelude::*, datapack::recipe::*};
+shaped!(["###", " | ", " | "], {
+    '#' => Ingredient::Item(Item::Diamond),
+    '|' => Ingredient::Item(Item::Stick),
+    'x' => Ingredient::Item(Item::Stone)
+}, RecipeResult {item: Item::DiamondPickaxe, count: 1});
+```
+*/
+
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+use crate::{core::{Identifier, serialize_tuple_map}, minecraft::*};
+
+/// An ingredient of a recipe
+pub enum Ingredient<'a, 'b> {
+    /// A single item
+    Item(Item),
+    /// Any item in an item tag
+    Tag(Identifier<'a, 'b>),
+    /// Any of the given ingredients
+    Any(&'a [Ingredient<'b, 'b>])
+}
+impl Serialize for Ingredient<'_, '_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        match self {
+            Self::Any(ingredients) => ingredients.serialize(serializer),
+            Self::Item(item) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("item", item)?;
+                map.end()
+            }
+            Self::Tag(tag) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("tag", tag)?;
+                map.end()
+            }
+        }
+    }
+}
+
+fn is_one(count: &i64) -> bool {*count == 1}
+
+/// The result of a crafting or smithing recipe
+#[derive(Serialize)]
+pub struct RecipeResult {
+    /// The item crafted
+    pub item: Item,
+    /// The amount crafted. Ignored by smithing recipes.
+    #[serde(skip_serializing_if = "is_one")] pub count: i64
+}
+
+/// A cooking recipe, used by the furnace-like variants of [`Recipe`]
+#[derive(Serialize)]
+pub struct Cooking<'a, 'b> {
+    /// The group of the recipe in the recipe book
+    #[serde(skip_serializing_if = "Option::is_none")] pub group: Option<&'a str>,
+    /// The item to cook
+    pub ingredient: Ingredient<'a, 'b>,
+    /// The cooked item
+    pub result: Item,
+    /// The experience given when the item is taken out
+    pub experience: f64,
+    /// The time to cook, in ticks
+    #[serde(rename = "cookingtime")] pub cooking_time: i64
+}
+
+/// A recipe. Use [`Datapack::recipe`](crate::datapack::Datapack::recipe).
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Recipe<'a, 'b> {
+    /// A shaped crafting recipe. Prefer creating this with [`shaped!`](crate::shaped).
+    CraftingShaped {
+        /// The group of the recipe in the recipe book
+        #[serde(skip_serializing_if = "Option::is_none")] group: Option<&'a str>,
+        /// The pattern, at most 3 rows of at most 3 characters each. A space is an empty slot.
+        pattern: &'a [&'b str],
+        /// The ingredient each character in the pattern stands for
+        #[serde(serialize_with = "serialize_key")] key: &'a [(char, Ingredient<'b, 'b>)],
+        /// The crafted item
+        result: RecipeResult
+    },
+    /// A shapeless crafting recipe
+    CraftingShapeless {
+        /// The group of the recipe in the recipe book
+        #[serde(skip_serializing_if = "Option::is_none")] group: Option<&'a str>,
+        /// The ingredients, in any order
+        ingredients: &'a [Ingredient<'b, 'b>],
+        /// The crafted item
+        result: RecipeResult
+    },
+    /// A furnace recipe
+    Smelting(Cooking<'a, 'b>),
+    /// A blast furnace recipe
+    Blasting(Cooking<'a, 'b>),
+    /// A smoker recipe
+    Smoking(Cooking<'a, 'b>),
+    /// A campfire recipe
+    CampfireCooking(Cooking<'a, 'b>),
+    /// A stonecutter recipe
+    Stonecutting {
+        /// The group of the recipe in the recipe book
+        #[serde(skip_serializing_if = "Option::is_none")] group: Option<&'a str>,
+        /// The item to cut
+        ingredient: Ingredient<'a, 'b>,
+        /// The cut item
+        result: Item,
+        /// The amount of items produced
+        count: i64
+    },
+    /// A smithing table recipe
+    Smithing {
+        /// The item to upgrade
+        base: Ingredient<'a, 'b>,
+        /// The item used to upgrade
+        addition: Ingredient<'a, 'b>,
+        /// The upgraded item. Its nbt is copied from the base.
+        result: RecipeResult
+    }
+}
+
+fn serialize_key<S: Serializer>(key: &&[(char, Ingredient<'_, '_>)], serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_tuple_map(&Some(*key), serializer)
+}
+
+/// Decodes the character starting at byte `i` of some utf-8, returning it and its length in bytes
+const fn decode_char(bytes: &[u8], i: usize) -> (u32, usize) {
+    let (len, mut c) = match bytes[i] {
+        0x00..=0x7f => return (bytes[i] as u32, 1),
+        0xc0..=0xdf => (2, (bytes[i] & 0x1f) as u32),
+        0xe0..=0xef => (3, (bytes[i] & 0x0f) as u32),
+        _ => (4, (bytes[i] & 0x07) as u32)
+    };
+    let mut j = 1;
+    while j < len {
+        c = c << 6 | (bytes[i + j] & 0x3f) as u32;
+        j += 1;
+    }
+    (c, len)
+}
+
+/// Whether `key` appears anywhere in `pattern`
+const fn pattern_contains(pattern: &[&str], key: char) -> bool {
+    let mut row = 0;
+    while row < pattern.len() {
+        let bytes = pattern[row].as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            let (c, len) = decode_char(bytes, i);
+            if c == key as u32 {
+                return true;
+            }
+            i += len;
+        }
+        row += 1;
+    }
+    false
+}
+
+/// Checks a shaped recipe's pattern, returning an error message if it is invalid. Used by [`shaped!`](crate::shaped).
+#[doc(hidden)]
+pub const fn check_pattern(pattern: &[&str], keys: &[char]) -> Option<&'static str> {
+    if pattern.is_empty() || pattern.len() > 3 {
+        return Some("a recipe pattern must have between 1 and 3 rows");
+    }
+    let mut width = 0;
+    let mut row = 0;
+    while row < pattern.len() {
+        let bytes = pattern[row].as_bytes();
+        let mut chars = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let (c, len) = decode_char(bytes, i);
+            if c != ' ' as u32 {
+                let mut found = false;
+                let mut key = 0;
+                while key < keys.len() {
+                    if keys[key] as u32 == c {
+                        found = true;
+                    }
+                    key += 1;
+                }
+                if !found {
+                    return Some("every character in a recipe pattern must have a key");
+                }
+            }
+            chars += 1;
+            i += len;
+        }
+        if chars == 0 || chars > 3 {
+            return Some("a recipe pattern row must have between 1 and 3 characters");
+        }
+        if row == 0 {
+            width = chars;
+        } else if chars != width {
+            return Some("every row of a recipe pattern must have the same width");
+        }
+        row += 1;
+    }
+    let mut key = 0;
+    while key < keys.len() {
+        if !pattern_contains(pattern, keys[key]) {
+            return Some("every key of a recipe must be used in its pattern");
+        }
+        key += 1;
+    }
+    None
+}
+
+/// Create a shaped [`Recipe`], checking the pattern at compile time.
+/// The pattern must be at most 3x3, every character (except spaces) must have a key, and every key must be used.
+/// ```
+/// # use copper::{shaped, prelude::*, datapack::recipe::*};
+/// shaped!(["##", "##"], {'#' => Ingredient::Tag(id!(planks))}, RecipeResult {item: Item::CraftingTable, count: 1});
+/// ```
+#[macro_export]
+macro_rules! shaped {
+    ([$($row:literal),+], {$($key:literal => $ingredient:expr),* $(,)?}, $result:expr) => {{
+        const _: () = if let Some(error) = $crate::datapack::recipe::check_pattern(&[$($row),+], &[$($key),*]) {
+            panic!("{}", error)
+        };
+        $crate::datapack::recipe::Recipe::CraftingShaped {
+            group: None,
+            pattern: &[$($row),+],
+            key: &[$(($key, $ingredient)),*],
+            result: $result
+        }
+    }};
+}
@@ -0,0 +1,115 @@
+/*!
+Contains the [`Tag`] struct.
+Tags are passed to a datapack via [`Datapack::tag`](crate::datapack::Datapack::tag).
+
+The type of a tag decides the folder it is written to, see [`TagType`]. For example:
+```
+# use copper::prelude::*;
+# use copper::datapack::tag::*;
+let ores: Tag<Block> = Tag {
+    replace: false,
+    values: &[
+        TagEntry::Value(Block::DiamondOre),
+        TagEntry::Tag {tag: id!(gold_ores), required: true},
+        TagEntry::Id {id: id!(othermod:ruby_ore), required: false}
+    ]
+};
+```
+*/
+
+use serde::{Serialize, Serializer, ser::SerializeMap};
+
+use crate::{core::Identifier, minecraft::*};
+
+/// A type which can be put in a [`Tag`]
+pub trait TagType: Serialize {
+    /// The folder the tag is written to, in `data/<namespace>/tags`
+    const FOLDER: &'static str;
+    /// Whether the tag holds functions, so commands can run it with `function #<tag>`
+    const FUNCTIONS: bool = false;
+    /// The function this value refers to, if it's in a function tag, so the pack can check that it exists
+    fn function(&self) -> Option<String> {
+        None
+    }
+}
+impl TagType for Block {
+    const FOLDER: &'static str = "blocks";
+}
+impl TagType for Item {
+    const FOLDER: &'static str = "items";
+}
+impl TagType for Entity {
+    const FOLDER: &'static str = "entity_types";
+}
+impl TagType for Fluid {
+    const FOLDER: &'static str = "fluids";
+}
+impl TagType for FunctionTag<'_, '_> {
+    const FOLDER: &'static str = "functions";
+    const FUNCTIONS: bool = true;
+    fn function(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+/// A function in a function tag, e.g `Tag::<FunctionTag>`. Function tags can be run like functions.
+#[derive(Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(transparent)]
+pub struct FunctionTag<'a, 'b>(pub Identifier<'a, 'b>);
+
+/// A fluid, used in fluid tags
+#[derive(Serialize, PartialEq, Eq, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum Fluid {
+    Water, FlowingWater, Lava, FlowingLava
+}
+
+/// An entry in a [`Tag`]
+pub enum TagEntry<'a, 'b, T: TagType> {
+    /// A single value
+    Value(T),
+    /// A value given by its id, e.g from another datapack or mod
+    Id {
+        /// The id of the value
+        id: Identifier<'a, 'b>,
+        /// Whether the tag fails to load if the value doesn't exist, instead of skipping it
+        required: bool
+    },
+    /// Every value in another tag, `#namespace:tag`
+    Tag {
+        /// The id of the tag
+        tag: Identifier<'a, 'b>,
+        /// Whether the tag fails to load if the other tag doesn't exist, instead of skipping it
+        required: bool
+    }
+}
+impl<T: TagType> Serialize for TagEntry<'_, '_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        match self {
+            Self::Value(value) => value.serialize(serializer),
+            Self::Id {id, required: true} => serializer.collect_str(id),
+            Self::Tag {tag, required: true} => serializer.collect_str(&format_args!("#{}", tag)),
+            Self::Id {id, required: false} => optional(serializer, id),
+            Self::Tag {tag, required: false} => optional(serializer, &format_args!("#{}", tag))
+        }
+    }
+}
+
+fn optional<S: Serializer>(serializer: S, id: &dyn std::fmt::Display) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry("id", &id.to_string())?;
+    map.serialize_entry("required", &false)?;
+    map.end()
+}
+
+/// A tag. Use [`Datapack::tag`](crate::datapack::Datapack::tag).
+#[derive(Serialize)]
+pub struct Tag<'a, 'b, T: TagType> {
+    /// Whether to replace the values of tags with the same name in lower priority datapacks, instead of merging with them
+    #[serde(skip_serializing_if = "std::ops::Not::not")] pub replace: bool,
+    /// The values in the tag
+    pub values: &'a [TagEntry<'b, 'b, T>]
+}
@@ -15,4 +15,6 @@ pub mod datapack;
 /// Contains core classes often used in commands/throught datapacks
 pub mod core;
 /// Contains the `Score` class, which can be used to manipulate scores in a friendly way.
-pub mod score;
\ No newline at end of file
+pub mod score;
+/// Contains the `StorageValue` class, which can be used to manipulate nbt storage in a friendly way.
+pub mod storage;
\ No newline at end of file
@@ -1,8 +1,18 @@
 #![allow(missing_docs)]
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 use serde::Serialize;
 
+/// The error returned when parsing an id which doesn't exist, e.g `"minecraft:foo".parse::<Block>()`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct UnknownIdError;
+impl Display for UnknownIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown id")
+    }
+}
+impl std::error::Error for UnknownIdError {}
+
 include!(concat!(env!("OUT_DIR"), "/blocks.rs"));
 include!(concat!(env!("OUT_DIR"), "/items.rs"));
 include!(concat!(env!("OUT_DIR"), "/entity.rs"));
@@ -3,5 +3,6 @@ pub use crate::core::{Identifier, sel::*};
 pub use crate::minecraft::{Block, Item, Entity};
 pub use crate::id;
 pub use crate::loc;
+pub use crate::nbt;
 /// Stable shorthand for `Default::default`. Useful in lots of datapack config structs.
 pub fn default<T: Default>() -> T {Default::default()}
\ No newline at end of file
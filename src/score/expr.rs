@@ -0,0 +1,179 @@
+use std::{convert::TryFrom, fmt::Display, ops::*};
+
+use super::ScoreValue;
+
+/// The objective holding temporary scores, e.g `#t0`
+pub (crate) const TEMP: &str = "copper.temp";
+/// The objective holding constants, e.g `#100`
+pub (crate) const CONST: &str = "copper.const";
+
+/// An operator used in a [`ScoreExpr`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScoreOperator {
+    #[doc = "Represents `+=`"] Add,
+    #[doc = "Represents `-=`"] Sub,
+    #[doc = "Represents `*=`"] Mul,
+    #[doc = "Represents `/=`, which rounds down"] Div,
+    #[doc = "Represents `%=`, which takes the sign of the divisor"] Rem
+}
+impl ScoreOperator {
+    fn commutative(self) -> bool {
+        matches!(self, Self::Add | Self::Mul)
+    }
+}
+impl Display for ScoreOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ScoreOperator::*;
+        write!(f, "{}", match self {
+            Add => "+=",
+            Sub => "-=",
+            Mul => "*=",
+            Div => "/=",
+            Rem => "%="
+        })
+    }
+}
+
+/// An arithmetic expression of scores and constants, which is lowered into commands by [`ScoreValue::set`].
+/// Build it using operators on `&ScoreValue`s, `ScoreExpr`s and `i64`s:
+/// ```
+/// # use copper::prelude::*;
+/// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+/// # let mut func = pack.function(id!(test:func));
+/// let global = pack.objective("global", "dummy", None);
+/// let (a, b, c) = (func.score("#a", &global), func.score("#b", &global), func.score("#c", &global));
+/// let x = func.score("#x", &global).set((&a + &b * 3) / (&c - 1));
+/// // scoreboard players operation #t0 copper.temp = #c global
+/// // scoreboard players remove #t0 copper.temp 1
+/// // scoreboard players operation #x global = #b global
+/// // scoreboard players operation #x global *= #3 copper.const
+/// // scoreboard players operation #x global += #a global
+/// // scoreboard players operation #x global /= #t0 copper.temp
+/// ```
+/// Temporary scores are stored in the `copper.temp` objective.
+/// Constants are stored in `copper.const`, and are set for the whole pack by the generated `copper:load` function.
+/// # Panics
+/// Lowering panics if a constant doesn't fit in a score, which is a 32-bit integer.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ScoreExpr<'a> {
+    /// A score, given by its name and objective
+    Score {
+        /// The name of the score holder
+        name: &'a str,
+        /// The objective of the score
+        objective: &'a str
+    },
+    /// A constant
+    Constant(i64),
+    /// An operation on two expressions
+    Operation(Box<ScoreExpr<'a>>, ScoreOperator, Box<ScoreExpr<'a>>)
+}
+impl ScoreExpr<'_> {
+    fn is(&self, name: &str, objective: &str) -> bool {
+        matches!(self, Self::Score {name: n, objective: o} if *n == name && *o == objective)
+    }
+}
+impl<'a> From<&ScoreValue<'a>> for ScoreExpr<'a> {
+    fn from(score: &ScoreValue<'a>) -> Self {
+        Self::Score {name: score.name, objective: score.objective}
+    }
+}
+impl From<i64> for ScoreExpr<'_> {
+    fn from(n: i64) -> Self {
+        Self::Constant(n)
+    }
+}
+
+macro_rules! expr_ops {
+    ($($trait:ident $method:ident $op:ident),*) => {$(
+        expr_ops!(@impl $trait $method $op, ScoreExpr<'a>, ScoreExpr<'a>);
+        expr_ops!(@impl $trait $method $op, ScoreExpr<'a>, &ScoreValue<'a>);
+        expr_ops!(@impl $trait $method $op, ScoreExpr<'a>, i64);
+        expr_ops!(@impl $trait $method $op, &ScoreValue<'a>, ScoreExpr<'a>);
+        expr_ops!(@impl $trait $method $op, &ScoreValue<'a>, &ScoreValue<'a>);
+        expr_ops!(@impl $trait $method $op, &ScoreValue<'a>, i64);
+        expr_ops!(@impl $trait $method $op, i64, ScoreExpr<'a>);
+        expr_ops!(@impl $trait $method $op, i64, &ScoreValue<'a>);
+    )*};
+    (@impl $trait:ident $method:ident $op:ident, $lhs:ty, $rhs:ty) => {
+        impl<'a> $trait<$rhs> for $lhs {
+            type Output = ScoreExpr<'a>;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                ScoreExpr::Operation(Box::new(self.into()), ScoreOperator::$op, Box::new(rhs.into()))
+            }
+        }
+    };
+}
+expr_ops!(Add add Add, Sub sub Sub, Mul mul Mul, Div div Div, Rem rem Rem);
+
+/// Convert a constant to a score, which is a 32-bit integer
+pub (crate) fn score_int(n: i64) -> i32 {
+    i32::try_from(n).unwrap_or_else(|_| panic!("the constant {} doesn't fit in a score, which is a 32-bit integer", n))
+}
+
+impl ScoreValue<'_> {
+    fn line(&self, line: impl Display) {
+        self.out.borrow_mut().line(line);
+    }
+    /// Get the fake player holding a constant, e.g `#100`, adding it to the pack's constant pool
+    pub (super) fn constant(&self, n: i64) -> String {
+        let n = score_int(n);
+        self.pack.borrow_mut().constant(n.into());
+        format!("#{}", n)
+    }
+    /// Write a command adding a constant to `name objective`, subtracting it if `negate` is set.
+    /// `add` and `remove` only take up to `i32::MAX`, but scores wrap around, so adding `i32::MIN` uses the constant pool instead.
+    pub (super) fn add_constant(&self, (name, objective): (&str, &str), n: i64, negate: bool) {
+        let n = if negate {score_int(n).wrapping_neg()} else {score_int(n)};
+        match n {
+            i32::MIN => {
+                let constant = self.constant(n.into());
+                self.line(format_args!("scoreboard players operation {} {} += {} {}", name, objective, constant, CONST));
+            }
+            n if n < 0 => self.line(format_args!("scoreboard players remove {} {} {}", name, objective, -n)),
+            n => self.line(format_args!("scoreboard players add {} {} {}", name, objective, n))
+        }
+    }
+    /// Write commands setting `name objective` to `expr`. Temporaries from `#t<temps>` onwards are free to use.
+    pub (super) fn lower(&self, expr: &ScoreExpr<'_>, (name, objective): (&str, &str), temps: usize) {
+        match expr {
+            ScoreExpr::Score {..} if expr.is(name, objective) => {}
+            ScoreExpr::Score {name: source, objective: source_objective} =>
+                self.line(format_args!("scoreboard players operation {} {} = {} {}", name, objective, source, source_objective)),
+            ScoreExpr::Constant(n) => self.line(format_args!("scoreboard players set {} {} {}", name, objective, score_int(*n))),
+            ScoreExpr::Operation(left, operator, right) => {
+                // Put the more complex side (or the destination itself) on the left, since the left is computed in place
+                let (left, right) = if operator.commutative() && !left.is(name, objective)
+                    && (right.is(name, objective) || matches!(**right, ScoreExpr::Operation(..)) && !matches!(**left, ScoreExpr::Operation(..))) {
+                    (right, left)
+                } else {(left, right)};
+                match (operator, &**right) {
+                    (ScoreOperator::Add | ScoreOperator::Sub, ScoreExpr::Constant(n)) => {
+                        self.lower(left, (name, objective), temps);
+                        if *n != 0 {
+                            self.add_constant((name, objective), *n, *operator == ScoreOperator::Sub);
+                        }
+                    }
+                    (_, ScoreExpr::Constant(n)) => {
+                        self.lower(left, (name, objective), temps);
+                        let constant = self.constant(*n);
+                        self.line(format_args!("scoreboard players operation {} {} {} {} {}", name, objective, operator, constant, CONST));
+                    }
+                    (_, ScoreExpr::Score {name: source, objective: source_objective}) if !right.is(name, objective) || left.is(name, objective) => {
+                        self.lower(left, (name, objective), temps);
+                        self.line(format_args!("scoreboard players operation {} {} {} {} {}", name, objective, operator, source, source_objective));
+                    }
+                    _ => {
+                        // The right side is computed first, so it reads the destination before it's overwritten
+                        self.pack.borrow_mut().internal_objective(TEMP);
+                        let temp = format!("#t{}", temps);
+                        self.lower(right, (&temp, TEMP), temps + 1);
+                        self.lower(left, (name, objective), temps + 1);
+                        self.line(format_args!("scoreboard players operation {} {} {} {} {}", name, objective, operator, temp, TEMP));
+                    }
+                }
+            }
+        }
+    }
+}
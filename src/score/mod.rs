@@ -1,35 +1,179 @@
-use std::{fs::File, io::Write, ops::*};
+use std::{cell::RefCell, fmt::Display, ops::*, rc::Rc};
+
+use crate::{core::{NumberRange, ScoreHolder}, datapack::{PackState, function::{Condition, ExecuteCondition, Output, ScoreComparison}}};
+
+pub use expr::*;
+
+mod expr;
+
+/// A scoreboard objective.
+/// Create using [`Datapack::objective()`](crate::datapack::Datapack::objective), which adds it when the pack is loaded.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Objective {
+    name: String
+}
+impl Objective {
+    pub (crate) fn new(name: &str) -> Self {
+        Self {name: name.to_string()}
+    }
+    /// The name of the objective
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl Display for Objective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
 
 /// A representation of a scoreboard value
-/// Create using `Datapack::score()`
+/// Create using [`Function::score()`](crate::datapack::function::Function::score)
 pub struct ScoreValue<'a> {
-    objective: &'a str,
-    name: &'a str,
-    out: File
+    pub (crate) objective: &'a str,
+    pub (crate) name: &'a str,
+    out: Rc<RefCell<Output>>,
+    pack: Rc<RefCell<PackState>>
 }
 impl<'a> ScoreValue<'a> {
-    pub (crate) fn new(name: &'a str, objective: &'a str, out: File) -> Self {
-        Self {objective, name, out}
+    pub (crate) fn new(name: &'a str, objective: &'a str, out: Rc<RefCell<Output>>, pack: Rc<RefCell<PackState>>) -> Self {
+        Self {objective, name, out, pack}
     }
     /// Set the scoreboard value to a constant
     #[must_use]
-    pub fn set_to(mut self, to: i64) -> Self {
-        writeln!(self.out, "scoreboard players set {} {} {}", self.name, self.objective, to).unwrap();
+    pub fn set_to(self, to: i64) -> Self {
+        self.out.borrow_mut().line(format_args!("scoreboard players set {} {} {}", self.name, self.objective, score_int(to)));
+        self
+    }
+    /// Set the scoreboard value to another score, or to a [`ScoreExpr`] built from scores and constants.
+    /// The expression is lowered into as few commands as possible, using temporary scores where needed.
+    /// Expressions don't borrow the scores they use, so one can read this score if it's built first:
+    /// `let expr = &a - &x; let x = x.set(expr);`
+    #[must_use]
+    pub fn set<'b>(self, to: impl Into<ScoreExpr<'b>>) -> Self {
+        self.lower(&to.into(), (self.name, self.objective), 0);
         self
     }
-    /// Set the scoreboard value to another score
+    /// Swap the scoreboard value with another score
     #[must_use]
-    pub fn set(mut self, to: &Self) -> Self {
-        writeln!(self.out, "scoreboard players operation {} {} = {} {}", self.name, self.objective, to.name, to.objective).unwrap();
+    pub fn swap(self, other: &Self) -> Self {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} >< {} {}", self.name, self.objective, other.name, other.objective));
         self
     }
+    /// Reset the scoreboard value, so it has no score
+    #[must_use]
+    pub fn reset(self) -> Self {
+        self.out.borrow_mut().line(format_args!("scoreboard players reset {} {}", self.name, self.objective));
+        self
+    }
+    /// Allow the score holder to use `/trigger` on this score's objective, which must have the `trigger` criterion
+    #[must_use]
+    pub fn enable(self) -> Self {
+        self.out.borrow_mut().line(format_args!("scoreboard players enable {} {}", self.name, self.objective));
+        self
+    }
+    /// Get the scoreboard value. The command's result is the score, so this is useful with `execute store`.
+    #[must_use]
+    pub fn get(self) -> Self {
+        self.out.borrow_mut().line(format_args!("scoreboard players get {} {}", self.name, self.objective));
+        self
+    }
+    /// Set the scoreboard value to the minimum of itself and another score or a constant, i.e `scoreboard players operation <this> < <other>`
+    #[must_use]
+    pub fn min<Rhs>(self, rhs: Rhs) -> Self where Self: Min<Rhs, Output = Self> {
+        Min::min(self, rhs)
+    }
+    /// Set the scoreboard value to the maximum of itself and another score or a constant, i.e `scoreboard players operation <this> > <other>`
+    #[must_use]
+    pub fn max<Rhs>(self, rhs: Rhs) -> Self where Self: Max<Rhs, Output = Self> {
+        Max::max(self, rhs)
+    }
+    fn compare(&self, comparison: ScoreComparison, other: &ScoreValue<'a>) -> Condition<'a> {
+        Condition {
+            check: ExecuteCondition::Score {
+                target: ScoreHolder::Name(self.name),
+                objective: self.objective,
+                comparison,
+                source: ScoreHolder::Name(other.name),
+                source_objective: other.objective
+            },
+            positive: true
+        }
+    }
+    /// A [`Condition`] passing if this score is less than another, i.e `if score <this> < <other>`.
+    /// Conditions can be used with [`Execute`](crate::datapack::function::Execute), and inverted with `!`:
+    /// ```
+    /// # use copper::{datapack::function::*, prelude::*};
+    /// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+    /// # let mut func = pack.function(id!(test:func));
+    /// let global = pack.objective("global", "dummy", None);
+    /// let (x, y) = (func.score("#x", &global), func.score("#y", &global));
+    /// assert_eq!(x.lt(&y).to_string(), "if score #x global < #y global");
+    /// assert_eq!((!x.in_range(1..=10)).to_string(), "unless score #x global matches 1..10");
+    /// func.run(Execute().condition(x.eq_const(5)).run(Kill())); // execute if score #x global matches 5 run kill
+    /// ```
+    pub fn lt(&self, other: &ScoreValue<'a>) -> Condition<'a> {
+        self.compare(ScoreComparison::Less, other)
+    }
+    /// A [`Condition`] passing if this score is less than or equal to another, i.e `if score <this> <= <other>`
+    pub fn le(&self, other: &ScoreValue<'a>) -> Condition<'a> {
+        self.compare(ScoreComparison::LessEqual, other)
+    }
+    /// A [`Condition`] passing if this score is greater than another, i.e `if score <this> > <other>`
+    pub fn gt(&self, other: &ScoreValue<'a>) -> Condition<'a> {
+        self.compare(ScoreComparison::Greater, other)
+    }
+    /// A [`Condition`] passing if this score is greater than or equal to another, i.e `if score <this> >= <other>`
+    pub fn ge(&self, other: &ScoreValue<'a>) -> Condition<'a> {
+        self.compare(ScoreComparison::GreaterEqual, other)
+    }
+    /// A [`Condition`] passing if this score is equal to another, i.e `if score <this> = <other>`
+    pub fn eq(&self, other: &ScoreValue<'a>) -> Condition<'a> {
+        self.compare(ScoreComparison::Equal, other)
+    }
+    /// A [`Condition`] passing if this score is equal to a constant, i.e `if score <this> matches <value>`
+    pub fn eq_const(&self, value: i64) -> Condition<'a> {
+        self.in_range(value)
+    }
+    /// A [`Condition`] passing if this score is in a range, i.e `if score <this> matches <range>`
+    pub fn in_range(&self, range: impl Into<NumberRange<i64>>) -> Condition<'a> {
+        Condition {
+            check: ExecuteCondition::ScoreMatches {target: ScoreHolder::Name(self.name), objective: self.objective, range: range.into()},
+            positive: true
+        }
+    }
+}
+/// Sets a score to the minimum of itself and another value, i.e `scoreboard players operation ... < ...`.
+/// [`ScoreValue::min`] calls this, so it doesn't need to be imported.
+pub trait Min<Rhs> {
+    /// The resulting type
+    type Output;
+    /// Performs the operation
+    fn min(self, rhs: Rhs) -> Self::Output;
+}
+/// Sets a score to the maximum of itself and another value, i.e `scoreboard players operation ... > ...`.
+/// [`ScoreValue::max`] calls this, so it doesn't need to be imported.
+pub trait Max<Rhs> {
+    /// The resulting type
+    type Output;
+    /// Performs the operation
+    fn max(self, rhs: Rhs) -> Self::Output;
 }
+
+impl ScoreValue<'_> {
+    /// Write `scoreboard players operation <this> <operator> <constant>`, using the pack's constant pool
+    fn const_operation(&self, operator: &str, n: i64) {
+        let constant = self.constant(n);
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} {} {} {}", self.name, self.objective, operator, constant, CONST));
+    }
+}
+
 impl Add<&Self> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn add(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} += {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn add(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} += {} {}", self.name, self.objective, rhs.name, rhs.objective));
         self
     }
 }
@@ -37,8 +181,8 @@ impl Add<i64> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn add(mut self, rhs: i64) -> Self::Output {
-        writeln!(self.out, "scoreboard players add {} {} {}", self.name, self.objective, rhs).unwrap();
+    fn add(self, rhs: i64) -> Self::Output {
+        self.add_constant((self.name, self.objective), rhs, false);
         self
     }
 }
@@ -46,8 +190,8 @@ impl Sub<&Self> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn sub(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} -= {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn sub(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} -= {} {}", self.name, self.objective, rhs.name, rhs.objective));
         self
     }
 }
@@ -55,8 +199,8 @@ impl Sub<i64> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn sub(mut self, rhs: i64) -> Self::Output {
-        writeln!(self.out, "scoreboard players remove {} {} {}", self.name, self.objective, rhs).unwrap();
+    fn sub(self, rhs: i64) -> Self::Output {
+        self.add_constant((self.name, self.objective), rhs, true);
         self
     }
 }
@@ -64,8 +208,16 @@ impl Mul<&Self> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn mul(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} *= {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn mul(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} *= {} {}", self.name, self.objective, rhs.name, rhs.objective));
+        self
+    }
+}
+impl Mul<i64> for ScoreValue<'_> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.const_operation("*=", rhs);
         self
     }
 }
@@ -73,37 +225,64 @@ impl Div<&Self> for ScoreValue<'_> {
     type Output = Self;
 
     #[must_use]
-    fn div(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} /= {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn div(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} /= {} {}", self.name, self.objective, rhs.name, rhs.objective));
         self
     }
 }
-impl Rem<&Self> for ScoreValue<'_> {
+impl Div<i64> for ScoreValue<'_> {
     type Output = Self;
 
-    fn rem(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} *= {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn div(self, rhs: i64) -> Self::Output {
+        self.const_operation("/=", rhs);
         self
     }
 }
-impl Shl<&Self> for ScoreValue<'_> {
+impl Rem<i64> for ScoreValue<'_> {
     type Output = Self;
 
-    fn shl(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} < {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn rem(self, rhs: i64) -> Self::Output {
+        self.const_operation("%=", rhs);
         self
     }
 }
-impl Shr<&Self> for ScoreValue<'_> {
+impl Min<i64> for ScoreValue<'_> {
     type Output = Self;
 
-    fn shr(mut self, rhs: &Self) -> Self::Output {
-        writeln!(self.out, "scoreboard players operation {} {} > {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+    fn min(self, rhs: i64) -> Self::Output {
+        self.const_operation("<", rhs);
         self
     }
 }
-impl BitOrAssign for ScoreValue<'_> {
-    fn bitor_assign(&mut self, rhs: Self) {
-        writeln!(self.out, "scoreboard players operation {} {} >< {} {}", self.name, self.objective, rhs.name, rhs.objective).unwrap();
+impl Max<i64> for ScoreValue<'_> {
+    type Output = Self;
+
+    fn max(self, rhs: i64) -> Self::Output {
+        self.const_operation(">", rhs);
+        self
     }
-}
\ No newline at end of file
+}
+impl Rem<&Self> for ScoreValue<'_> {
+    type Output = Self;
+
+    fn rem(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} %= {} {}", self.name, self.objective, rhs.name, rhs.objective));
+        self
+    }
+}
+impl Min<&Self> for ScoreValue<'_> {
+    type Output = Self;
+
+    fn min(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} < {} {}", self.name, self.objective, rhs.name, rhs.objective));
+        self
+    }
+}
+impl Max<&Self> for ScoreValue<'_> {
+    type Output = Self;
+
+    fn max(self, rhs: &Self) -> Self::Output {
+        self.out.borrow_mut().line(format_args!("scoreboard players operation {} {} > {} {}", self.name, self.objective, rhs.name, rhs.objective));
+        self
+    }
+}
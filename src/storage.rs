@@ -0,0 +1,81 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{core::{DataTarget, Identifier, nbt::{Nbt, NbtPath}}, datapack::function::{DataGet, DataModify, DataModifyOperation, DataModifySource, DataRemove, Execute, NumericType, Output, ScoreGet, StoreTarget}, score::ScoreValue};
+
+/// A representation of a value in nbt storage, `storage <storage> <path>`, which can hold any nbt.
+/// Scores are limited to ints, so this is used for other values like floats and strings.
+/// Its methods return the value again, so they can be chained.
+/// Create using [`Function::storage()`](crate::datapack::function::Function::storage)
+/// ```
+/// # use copper::{core::nbt::{Nbt, NbtPath}, datapack::function::NumericType, prelude::*};
+/// # let pack = Datapack::new(std::env::temp_dir().join("copper_doc"));
+/// # let mut func = pack.function(id!(test:func));
+/// let global = pack.objective("global", "dummy", None);
+/// let speed = func.storage(id!(test:vars), NbtPath::root("speed"));
+/// let score = func.score("#speed", &global);
+/// speed.set(Nbt::Double(1.5)).set_from_score(&score, NumericType::Double, 0.01).copy_to_score(&score, 100.0);
+/// // data modify storage test:vars speed set value 1.5d
+/// // execute store result storage test:vars speed double 0.01 run scoreboard players get #speed global
+/// // execute store result score #speed global run data get storage test:vars speed 100
+/// ```
+pub struct StorageValue<'a> {
+    storage: Identifier<'a, 'a>,
+    path: NbtPath,
+    out: Rc<RefCell<Output>>
+}
+impl<'a> StorageValue<'a> {
+    pub (crate) fn new(storage: Identifier<'a, 'a>, path: NbtPath, out: Rc<RefCell<Output>>) -> Self {
+        Self {storage, path, out}
+    }
+    /// The storage this value is in
+    pub fn storage(&self) -> Identifier<'a, 'a> {
+        self.storage
+    }
+    /// The path of this value in its storage
+    pub fn path(&self) -> &NbtPath {
+        &self.path
+    }
+    fn modify(&self, operation: DataModifyOperation, source: DataModifySource<'_>) {
+        self.out.borrow_mut().run(DataModify(DataTarget::Storage(self.storage), self.path.clone(), operation, source));
+    }
+    fn source(&self) -> DataModifySource<'a> {
+        DataModifySource::From(DataTarget::Storage(self.storage), Some(self.path.clone()))
+    }
+    /// Set the value to some nbt
+    pub fn set(&self, nbt: Nbt) -> &Self {
+        self.modify(DataModifyOperation::Set, DataModifySource::Value(nbt));
+        self
+    }
+    /// Set the value to a copy of another value
+    pub fn set_from(&self, other: &StorageValue<'_>) -> &Self {
+        self.modify(DataModifyOperation::Set, other.source());
+        self
+    }
+    /// Add some nbt to the end of the value, which must be a list
+    pub fn append(&self, nbt: Nbt) -> &Self {
+        self.modify(DataModifyOperation::Append, DataModifySource::Value(nbt));
+        self
+    }
+    /// Add a copy of another value to the end of the value, which must be a list
+    pub fn append_from(&self, other: &StorageValue<'_>) -> &Self {
+        self.modify(DataModifyOperation::Append, other.source());
+        self
+    }
+    /// Remove the value from its storage
+    pub fn remove(&self) -> &Self {
+        self.out.borrow_mut().run(DataRemove(DataTarget::Storage(self.storage), self.path.clone()));
+        self
+    }
+    /// Set the value to a score multiplied by `scale`, stored as the numeric type `ty`
+    pub fn set_from_score(&self, score: &ScoreValue<'_>, ty: NumericType, scale: f64) -> &Self {
+        let target = StoreTarget::Storage(self.storage, self.path.clone(), ty, scale);
+        self.out.borrow_mut().execute_run(Execute().store_result(target).run(ScoreGet(score.name.into(), score.objective)));
+        self
+    }
+    /// Set a score to the value multiplied by `scale`, rounded down. The value must be a number.
+    pub fn copy_to_score(&self, score: &ScoreValue<'_>, scale: f64) -> &Self {
+        let get = DataGet {path: Some((self.path.clone(), Some(scale))), ..DataGet(DataTarget::Storage(self.storage))};
+        self.out.borrow_mut().execute_run(Execute().store_result(StoreTarget::Score(score.name.into(), score.objective)).run(get));
+        self
+    }
+}
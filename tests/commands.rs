@@ -0,0 +1,88 @@
+use copper::{core::{Coordinate, NumberRange, ScoreHolder, text::{TextColor, TextComponent}}, datapack::function::*, prelude::*};
+
+mod common;
+use common::*;
+
+#[test]
+fn execute() {
+    assert_eq!(commands("execute", |_, func| {
+        func.run(Execute()
+            .as_(at_a())
+            .at(at_s())
+            .positioned(loc!(~0 ~1 ~0))
+            .positioned_as(at_p())
+            .rotated(Coordinate::Relative(0.0), Coordinate::Absolute(90.0))
+            .rotated_as(at_s())
+            .facing(loc!(^ ^ ^1))
+            .facing_entity(at_e(), Anchor::Eyes)
+            .align(Swizzle::new(true, false, true))
+            .anchored(Anchor::Feet)
+            .in_(id!(the_nether))
+            .if_(ExecuteCondition::Block(loc!(~0 ~-1 ~0), Block::Stone))
+            .unless(ExecuteCondition::Entity(Box::new(at_e())))
+            .if_(ExecuteCondition::Predicate(id!(test:is_day)))
+            .if_(ExecuteCondition::ScoreMatches {target: ScoreHolder::from("#x"), objective: "global", range: NumberRange {min: Some(1), max: None}})
+            .store_result(StoreTarget::Score(ScoreHolder::from("#y"), "global"))
+            .store_success(StoreTarget::Bossbar(id!(test:bar), BossbarValue::Max))
+            .run(Say("hi")));
+        func.run(Execute().if_(ExecuteCondition::Blocks {start: loc!(0 0 0), end: loc!(1 1 1), destination: loc!(2 2 2), mode: BlocksMode::Masked}));
+    }), [
+        "execute as @a at @s positioned ~ ~1 ~ positioned as @p rotated ~ 90 rotated as @s facing ^ ^ ^1 facing entity @e eyes \
+         align xz anchored feet in minecraft:the_nether if block ~ ~-1 ~ stone unless entity @e if predicate test:is_day \
+         if score #x global matches 1.. store result score #y global store success bossbar test:bar max run say hi",
+        "execute if blocks 0 0 0 1 1 1 2 2 2 masked"
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "a swizzle must contain at least one axis")]
+fn empty_swizzle() {
+    let _ = Swizzle::new(false, false, false);
+}
+
+#[test]
+fn scoped_execute() {
+    assert_eq!(commands("scoped_execute", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        func.with_execute(Execute().as_(at_a()), |func| {
+            func.run(Kill());
+            func.with_execute(Execute().at(at_s()).if_(ExecuteCondition::Entity(Box::new(at_s().tag("alive", true)))), |func| {
+                func.run(Setblock(loc!(~0 ~0 ~0), Block::Stone));
+                let _ = func.score("@s", &global).set_to(1);
+            });
+            func.run(Execute().at(at_s()).run(Say("nested")));
+        });
+        func.run(Kill());
+    }), [
+        "execute as @a run kill",
+        "execute as @a at @s if entity @s[tag=alive] run setblock ~ ~ ~ stone",
+        "execute as @a at @s if entity @s[tag=alive] run scoreboard players set @s global 1",
+        "execute as @a run execute at @s run say nested",
+        "kill"
+    ]);
+}
+
+#[test]
+fn text_commands() {
+    assert_eq!(commands("text_commands", |_, func| {
+        func.run(Tellraw(at_a(), TextComponent::text("Hello").color(TextColor::Red)));
+        func.run(Title(at_p(), TitleAction::Title("Welcome".into())));
+        func.run(Title(at_p(), TitleAction::Subtitle("to the server".into())));
+        func.run(Title(at_a(), TitleAction::Actionbar(TextComponent::text("Low health").bold(true))));
+        func.run(Title(at_a(), TitleAction::Times {fade_in: 10, stay: 70, fade_out: 20}));
+        func.run(Title(at_a(), TitleAction::Clear));
+        func.run(Title(at_a(), TitleAction::Reset));
+        func.run(Say("Server restarting"));
+        func.run(Msg(at_p(), "psst"));
+    }), [
+        r#"tellraw @a {"text":"Hello","color":"red"}"#,
+        r#"title @p title {"text":"Welcome"}"#,
+        r#"title @p subtitle {"text":"to the server"}"#,
+        r#"title @a actionbar {"text":"Low health","bold":true}"#,
+        "title @a times 10 70 20",
+        "title @a clear",
+        "title @a reset",
+        "say Server restarting",
+        "msg @p psst"
+    ]);
+}
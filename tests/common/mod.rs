@@ -0,0 +1,53 @@
+//! Helpers shared by the integration tests, which build packs in a temporary folder and read back what they wrote
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use copper::{datapack::{DanglingReferences, function::Function}, prelude::*};
+
+/// Create a fresh pack named `name` in a temporary folder, returning it and its `data` folder
+fn pack(name: &str) -> (Datapack, PathBuf) {
+    let dir = std::env::temp_dir().join("copper_test").join(env!("CARGO_CRATE_NAME")).join(name);
+    (Datapack::new(&dir), dir.join("data"))
+}
+
+/// Build a fresh pack named `name` with `body`, returning the pack's `data` folder once it has been finished
+pub fn build(name: &str, body: impl FnOnce(&Datapack)) -> PathBuf {
+    let (pack, data) = pack(name);
+    body(&pack);
+    pack.finish().unwrap();
+    data
+}
+
+/// Build a fresh pack named `name` with `body`, returning the result of finishing it
+pub fn finish(name: &str, body: impl FnOnce(&Datapack)) -> Result<(), DanglingReferences> {
+    let (pack, _) = pack(name);
+    body(&pack);
+    pack.finish()
+}
+
+/// Read a generated file relative to the `data` folder
+pub fn read(data: &Path, path: &str) -> String {
+    std::fs::read_to_string(data.join(path)).unwrap()
+}
+
+/// Read the lines of a generated file relative to the `data` folder
+pub fn lines(data: &Path, path: &str) -> Vec<String> {
+    read(data, path).lines().map(String::from).collect()
+}
+
+/// Run `body` in a fresh function `test:func`, returning the commands written to each of `functions`, relative to `test:func`.
+/// An empty name stands for `test:func` itself.
+pub fn functions<const N: usize>(name: &str, functions: [&str; N], body: impl FnOnce(&Datapack, &mut Function)) -> [Vec<String>; N] {
+    let data = build(name, |pack| body(pack, &mut pack.function(id!(test:func))));
+    functions.map(|function| match function {
+        "" => lines(&data, "test/functions/func.mcfunction"),
+        function => lines(&data, &format!("test/functions/func/{}.mcfunction", function))
+    })
+}
+
+/// Run `body` in a fresh function `test:func`, returning the commands it wrote
+pub fn commands(name: &str, body: impl FnOnce(&Datapack, &mut Function)) -> Vec<String> {
+    let [commands] = functions(name, [""], body);
+    commands
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+
+use copper::{core::{DataTarget, nbt::{Nbt, NbtPath}}, datapack::function::*, prelude::*};
+
+mod common;
+use common::*;
+
+#[test]
+fn if_else() {
+    let [func, then, nested, otherwise] = functions("if_else", ["", "__if_0", "__if_1", "__else_2"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("#x", &global);
+        func.if_(x.eq_const(0), |f| {
+            f.run(Say("zero"));
+            f.if_(!x.eq_const(0), |f| f.run(Say("unreachable")));
+        }).else_(|f| f.run(Say("not zero")));
+    });
+    assert_eq!(func, [
+        "execute store success score #if copper.temp if score #x global matches 0",
+        "execute if score #if copper.temp matches 1 run function test:func/__if_0",
+        "execute if score #if copper.temp matches 0 run function test:func/__else_2"
+    ]);
+    assert_eq!(then, [
+        "say zero",
+        "execute unless score #x global matches 0 run function test:func/__if_1",
+        "scoreboard players set #if copper.temp 1"
+    ]);
+    assert_eq!(nested, ["say unreachable"]);
+    assert_eq!(otherwise, ["say not zero"]);
+}
+
+#[test]
+fn if_in_execute() {
+    let [func] = functions("if_in_execute", [""], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("#x", &global);
+        func.with_execute(Execute().as_(at_a()), |f| {
+            f.if_(x.gt(&x), |f| f.run(Say("never")));
+        });
+    });
+    assert_eq!(func, ["execute as @a if score #x global > #x global run function test:func/__if_0"]);
+}
+
+#[test]
+fn if_else_in_execute() {
+    let [func, dispatch] = functions("if_else_in_execute", ["", "__anon_2"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("@s", &global);
+        func.with_execute(Execute().as_(at_a()), |f| {
+            f.if_(x.eq_const(0), |f| f.run(Say("zero"))).else_(|f| f.run(Say("not zero")));
+        });
+    });
+    assert_eq!(func, ["execute as @a run function test:func/__anon_2"]);
+    assert_eq!(dispatch, [
+        "execute store success score #if copper.temp if score @s global matches 0",
+        "execute if score #if copper.temp matches 1 run function test:func/__if_0",
+        "execute if score #if copper.temp matches 0 run function test:func/__else_1"
+    ]);
+}
+
+#[test]
+fn switch() {
+    let [func, low, high, high_node, case] = functions("switch", ["", "__switch_0", "__switch_3", "__switch_5", "__case_6"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("#x", &global);
+        func.switch(&x, 0..5, |f, case| f.run(Say(["a", "b", "c", "d", "e"][case as usize])));
+    });
+    assert_eq!(func, [
+        "scoreboard players operation #switch copper.temp = #x global",
+        "execute if score #switch copper.temp matches 0..1 run function test:func/__switch_0",
+        "execute if score #switch copper.temp matches 2..4 run function test:func/__switch_3"
+    ]);
+    assert_eq!(low, [
+        "execute if score #switch copper.temp matches 0 run function test:func/__case_1",
+        "execute if score #switch copper.temp matches 1 run function test:func/__case_2"
+    ]);
+    assert_eq!(high, [
+        "execute if score #switch copper.temp matches 2 run function test:func/__case_4",
+        "execute if score #switch copper.temp matches 3..4 run function test:func/__switch_5"
+    ]);
+    assert_eq!(high_node, [
+        "execute if score #switch copper.temp matches 3 run function test:func/__case_6",
+        "execute if score #switch copper.temp matches 4 run function test:func/__case_7"
+    ]);
+    assert_eq!(case, ["say d", "scoreboard players set #switch copper.temp 3"]);
+}
+
+#[test]
+fn switch_in_execute() {
+    let [func, dispatch] = functions("switch_in_execute", ["", "__anon_0"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("@s", &global);
+        func.with_execute(Execute().as_(at_a()), |f| {
+            f.switch(&x, 0..2, |f, case| f.run(Say(["a", "b"][case as usize])));
+        });
+    });
+    assert_eq!(func, ["execute as @a run function test:func/__anon_0"]);
+    assert_eq!(dispatch, [
+        "scoreboard players operation #switch copper.temp = @s global",
+        "execute if score #switch copper.temp matches 0 run function test:func/__case_1",
+        "execute if score #switch copper.temp matches 1 run function test:func/__case_2"
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "the constant -2147483649 doesn't fit in a score")]
+fn switch_out_of_range() {
+    commands("switch_out_of_range", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let state = func.score("#state", &global);
+        func.switch(&state, i64::from(i32::MIN) - 1..0, |f, _| f.run(Say("case")));
+    });
+}
+
+#[test]
+fn while_loop() {
+    let [func, body] = functions("while_loop", ["", "__while_0"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("#x", &global);
+        func.while_(x.in_range(1..), |f| {
+            let _ = f.score("#x", &global) - 1;
+        });
+    });
+    assert_eq!(func, ["execute if score #x global matches 1.. run function test:func/__while_0"]);
+    assert_eq!(body, [
+        "scoreboard players remove #x global 1",
+        "execute if score #x global matches 1.. run function test:func/__while_0"
+    ]);
+}
+
+#[test]
+fn while_loop_in_execute() {
+    let [func, start, body] = functions("while_loop_in_execute", ["", "__anon_1", "__while_0"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("@s", &global);
+        func.with_execute(Execute().as_(at_a()), |f| f.while_(x.in_range(1..), |f| f.run(Say("loop"))));
+    });
+    assert_eq!(func, ["execute as @a run function test:func/__anon_1"]);
+    assert_eq!(start, ["execute if score @s global matches 1.. run function test:func/__while_0"]);
+    assert_eq!(body, [
+        "say loop",
+        "execute if score @s global matches 1.. run function test:func/__while_0"
+    ]);
+}
+
+#[test]
+fn for_loop() {
+    let [func, body] = functions("for_loop", ["", "__for_0"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let i = func.score("#i", &global);
+        func.for_range(&i, -2..3, |f| f.run(Say("loop")));
+        func.for_range(&i, 3..3, |f| f.run(Say("never")));
+    });
+    assert_eq!(func, [
+        "scoreboard players set #i global -2",
+        "execute if score #i global matches ..2 run function test:func/__for_0"
+    ]);
+    assert_eq!(body, [
+        "say loop",
+        "scoreboard players add #i global 1",
+        "execute if score #i global matches ..2 run function test:func/__for_0"
+    ]);
+}
+
+#[test]
+fn for_loop_in_execute() {
+    let [func, start] = functions("for_loop_in_execute", ["", "__anon_0"], |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let i = func.score("@s", &global);
+        func.with_execute(Execute().as_(at_a()), |f| f.for_range(&i, 0..3, |f| f.run(Say("loop"))));
+    });
+    assert_eq!(func, ["execute as @a run function test:func/__anon_0"]);
+    assert_eq!(start, [
+        "scoreboard players set @s global 0",
+        "execute if score @s global matches ..2 run function test:func/__for_1"
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "the constant 2147483648 doesn't fit in a score")]
+fn for_loop_out_of_range() {
+    commands("for_loop_out_of_range", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let i = func.score("#i", &global);
+        func.for_range(&i, 0..i64::from(i32::MAX) + 2, |f| f.run(Say("loop")));
+    });
+}
+
+#[test]
+fn anonymous_functions() {
+    let [func, called, scheduled, nested] = functions("anonymous_functions", ["", "__anon_0", "__anon_1", "__anon_2"], |_, func| {
+        assert_eq!(func.call(|f| f.run(Say("now"))), "test:func/__anon_0");
+        func.with_execute(Execute().as_(at_a()), |f| {
+            f.schedule(5, |f| {
+                f.call(|f| f.run(Say("later")));
+            });
+        });
+    });
+    assert_eq!(func, [
+        "function test:func/__anon_0",
+        "execute as @a run schedule function test:func/__anon_1 5t"
+    ]);
+    assert_eq!(called, ["say now"]);
+    assert_eq!(scheduled, ["function test:func/__anon_2"]);
+    assert_eq!(nested, ["say later"]);
+}
+
+#[test]
+fn anonymous_functions_in_folders() {
+    let data = build("anonymous_functions_in_folders", |pack| {
+        let mut func = pack.function(id!(test:dir/main));
+        func.run(Say("before"));
+        assert_eq!(func.schedule(20, |f| f.run(Say("later"))), "test:dir/main/__anon_0");
+        func.run(Say("after"));
+    });
+    assert_eq!(lines(&data, "test/functions/dir/main.mcfunction"), [
+        "say before",
+        "schedule function test:dir/main/__anon_0 20t",
+        "say after"
+    ]);
+    assert_eq!(lines(&data, "test/functions/dir/main/__anon_0.mcfunction"), ["say later"]);
+}
+
+#[test]
+fn function_commands() {
+    let [func] = functions("function_commands", [""], |pack, func| {
+        use copper::datapack::tag::*;
+        pack.function(id!(test:other));
+        pack.tag(id!(test:group), Tag {replace: false, values: &[TagEntry::Value(FunctionTag(id!(test:other)))]});
+        pack.external_function(id!(lib:api), false);
+        func.run(FunctionCall(id!(test:other)));
+        func.run(FunctionCall {tag: true, ..FunctionCall(id!(test:group))});
+        func.run(FunctionCall {arguments: Some(MacroArguments::Compound(BTreeMap::from([(String::from("x"), Nbt::Int(1)), (String::from("name"), Nbt::from("a b"))]))), ..FunctionCall(id!(lib:api))});
+        func.run(FunctionCall {
+            arguments: Some(MacroArguments::With(DataTarget::Storage(id!(test:args)), Some(NbtPath::root("call")))),
+            ..FunctionCall(id!(test:other))
+        });
+        func.run(FunctionCall {arguments: Some(MacroArguments::With(at_s().into(), None)), ..FunctionCall(id!(test:other))});
+        func.run(Schedule(id!(test:other), 20));
+        func.run(Schedule {tag: true, mode: Some(ScheduleMode::Append), ..Schedule(id!(test:group), 1)});
+        func.run(ScheduleClear(id!(test:other)));
+        func.run(Return(3));
+        func.run(ReturnRun(FunctionCall(id!(test:func))));
+        func.run(Execute().as_(at_a()).run(FunctionCall {tag: true, ..FunctionCall(id!(tick))}));
+        pack.tick_function(id!(test:tick));
+    });
+    assert_eq!(func, [
+        "function test:other",
+        "function #test:group",
+        r#"function lib:api {name:"a b",x:1}"#,
+        "function test:other with storage test:args call",
+        "function test:other with entity @s",
+        "schedule function test:other 20t",
+        "schedule function #test:group 1t append",
+        "schedule clear test:other",
+        "return 3",
+        "return run function test:func",
+        "execute as @a run function #minecraft:tick"
+    ]);
+}
+
+#[test]
+fn dangling_function() {
+    let error = finish("dangling_function", |pack| {
+        let mut func = pack.function(id!(test:func));
+        func.run(FunctionCall(id!(test:missing)));
+        func.if_(Condition {check: ExecuteCondition::Entity(Box::new(at_s())), positive: true}, |f| f.run(ScheduleClear {tag: true, ..ScheduleClear(id!(test:missing_tag))}));
+    }).unwrap_err();
+    assert_eq!(error.to_string(), "the pack refers to functions which don't exist: \
+        `test:missing` (referenced in `test:func`), `#test:missing_tag` (referenced in `test:func`)");
+}
+
+#[test]
+fn only_function_tags_are_callable() {
+    use copper::datapack::tag::*;
+    let error = finish("only_function_tags_are_callable", |pack| {
+        pack.tag(id!(test:blocks), Tag::<Block> {replace: false, values: &[TagEntry::Value(Block::Stone)]});
+        pack.function(id!(test:func)).run(FunctionCall {tag: true, ..FunctionCall(id!(test:blocks))});
+    }).unwrap_err();
+    assert_eq!(error.references, [(String::from("#test:blocks"), String::from("test:func"))]);
+}
+
+#[test]
+fn dangling_tag_entries_and_rewards() {
+    use copper::datapack::{advancement::*, tag::*};
+    let error = finish("dangling_tag_entries_and_rewards", |pack| {
+        pack.function(id!(test:func));
+        pack.tag(id!(test:group), Tag {replace: false, values: &[
+            TagEntry::Value(FunctionTag(id!(test:func))),
+            TagEntry::Value(FunctionTag(id!(test:missing))),
+            TagEntry::Id {id: id!(other:missing), required: true},
+            TagEntry::Id {id: id!(other:optional), required: false},
+            TagEntry::Tag {tag: id!(other:missing_tag), required: true},
+            TagEntry::Tag {tag: id!(other:optional_tag), required: false}
+        ]});
+        pack.advancement(id!(test:adv), Advancement {
+            rewards: Some(AdvancementRewards {function: Some(id!(test:reward)), ..default()}),
+            ..default()
+        });
+    }).unwrap_err();
+    let references: Vec<_> = error.references.iter().map(|(reference, from)| (reference.as_str(), from.as_str())).collect();
+    assert_eq!(references, [
+        ("test:missing", "#test:group"),
+        ("other:missing", "#test:group"),
+        ("#other:missing_tag", "#test:group"),
+        ("test:reward", "advancement test:adv")
+    ]);
+}
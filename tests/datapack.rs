@@ -0,0 +1,145 @@
+use copper::{core::{Identifier, text::*}, datapack::{advancement::*, item_modifier::*, loot_table::*, predicate::{ItemPredicate, Predicate}, recipe::*, tag::*}, prelude::*, shaped};
+
+mod common;
+use common::*;
+
+#[test]
+fn loot_tables() {
+    let data = build("loot_tables", |pack| pack.loot_table(id!(test:entities/cow), LootTable {
+        ty: Some(LootContextType::Entity),
+        pools: &[LootPool {
+            rolls: NumberProvider::Uniform {min: Box::new(NumberProvider::Constant(1)), max: Box::new(NumberProvider::Constant(2))},
+            entries: &[
+                LootEntry::Item {
+                    name: Item::Leather,
+                    properties: EntryProperties {
+                        functions: Some(&[
+                            ItemModifier::SetCount {count: NumberProvider::Constant(2), add: false},
+                            ItemModifier::LimitCountExact {limit: NumberProvider::Constant(3)}
+                        ]),
+                        ..default()
+                    }
+                },
+                LootEntry::LootTable {name: id!(test:extra/drops), properties: EntryProperties {weight: Some(2), ..default()}}
+            ],
+            conditions: Some(&[Predicate::KilledByPlayer {inverse: false}]),
+            ..default()
+        }],
+        functions: Some(&[ItemModifier::FurnaceSmelt])
+    }));
+    assert_eq!(read(&data, "test/loot_tables/entities/cow.json"), concat!(
+        r#"{"type":"entity","pools":[{"rolls":{"type":"uniform","min":1,"max":2},"entries":["#,
+        r#"{"type":"item","name":"leather","functions":[{"function":"set_count","count":2},{"function":"limit_count","limit":3}]},"#,
+        r#"{"type":"loot_table","name":"test:extra/drops","weight":2}],"#,
+        r#""conditions":[{"condition":"killed_by_player"}]}],"functions":[{"function":"furnace_smelt"}]}"#
+    ));
+}
+
+#[test]
+fn advancements() {
+    let data = build("advancements", |pack| {
+        pack.external_function(id!(test:reward), false);
+        pack.advancement(id!(test:story/dirt), Advancement {
+            parent: Some(id!(minecraft:story/root)),
+            display: Some(AdvancementDisplay {
+                icon: AdvancementIcon {item: Item::Dirt, nbt: Some(nbt!{CustomModelData: 3})},
+                title: "Dirty".into(),
+                description: TextComponent::text("Pick up dirt").color(TextColor::Gold),
+                frame: Some(AdvancementFrame::Goal),
+                background: None,
+                show_toast: None,
+                announce_to_chat: Some(false),
+                hidden: None
+            }),
+            criteria: &[
+                ("got_dirt", Trigger::InventoryChanged(InventoryChangedConditions {
+                    items: Some(&[ItemPredicate {item: Some(Item::Dirt), ..default()}]),
+                    ..default()
+                })),
+                ("never", Trigger::Impossible)
+            ],
+            requirements: Some(&[&["got_dirt", "never"]]),
+            rewards: Some(AdvancementRewards {experience: Some(10), function: Some(id!(test:reward)), ..default()})
+        });
+    });
+    assert_eq!(read(&data, "test/advancements/story/dirt.json"), concat!(
+        r#"{"parent":"minecraft:story/root","#,
+        r#""display":{"icon":{"item":"dirt","nbt":"{CustomModelData:3}"},"title":{"text":"Dirty"},"description":{"text":"Pick up dirt","color":"gold"},"frame":"goal","announce_to_chat":false},"#,
+        r#""criteria":{"got_dirt":{"trigger":"inventory_changed","conditions":{"items":[{"item":"dirt"}]}},"never":{"trigger":"impossible"}},"#,
+        r#""requirements":[["got_dirt","never"]],"rewards":{"experience":10,"function":"test:reward"}}"#
+    ));
+}
+
+#[test]
+fn recipes() {
+    let data = build("recipes", |pack| {
+        pack.recipe(id!(test:pickaxe), shaped!(["###", " | ", " | "], {
+            '#' => Ingredient::Item(Item::Diamond),
+            '|' => Ingredient::Any(&[Ingredient::Item(Item::Stick), Ingredient::Tag(id!(test:rods))])
+        }, RecipeResult {item: Item::DiamondPickaxe, count: 1}));
+        pack.recipe(id!(test:glass), Recipe::Smelting(Cooking {
+            group: None,
+            ingredient: Ingredient::Tag(id!(sand)),
+            result: Item::Glass,
+            experience: 0.1,
+            cooking_time: 200
+        }));
+    });
+    assert_eq!(read(&data, "test/recipes/pickaxe.json"), concat!(
+        r####"{"type":"crafting_shaped","pattern":["###"," | "," | "],"####,
+        r####""key":{"#":{"item":"diamond"},"|":[{"item":"stick"},{"tag":"test:rods"}]},"result":{"item":"diamond_pickaxe"}}"####
+    ));
+    assert_eq!(read(&data, "test/recipes/glass.json"),
+        r#"{"type":"smelting","ingredient":{"tag":"minecraft:sand"},"result":"glass","experience":0.1,"cookingtime":200}"#);
+}
+
+#[test]
+fn recipe_patterns() {
+    assert_eq!(check_pattern(&["##", "##"], &['#']), None);
+    assert_eq!(check_pattern(&["é ", " é"], &['é']), None);
+    assert_eq!(check_pattern(&["ééé", "é"], &['é']), Some("every row of a recipe pattern must have the same width"));
+    assert_eq!(check_pattern(&["éééé"], &['é']), Some("a recipe pattern row must have between 1 and 3 characters"));
+    assert_eq!(check_pattern(&["Ã"], &['é']), Some("every character in a recipe pattern must have a key"));
+    assert_eq!(check_pattern(&["#"], &['#', '|']), Some("every key of a recipe must be used in its pattern"));
+    assert_eq!(check_pattern(&["#"], &['#', ' ']), Some("every key of a recipe must be used in its pattern"));
+    assert_eq!(check_pattern(&["#", "#", "#", "#"], &['#']), Some("a recipe pattern must have between 1 and 3 rows"));
+}
+
+#[test]
+fn tags() {
+    let data = build("tags", |pack| {
+        pack.tag(id!(test:ores), Tag {replace: true, values: &[
+            TagEntry::Value(Block::DiamondOre),
+            TagEntry::Tag {tag: id!(gold_ores), required: true},
+            TagEntry::Id {id: id!(othermod:ruby_ore), required: false}
+        ]});
+        pack.tag(id!(test:liquids/hot), Tag {replace: false, values: &[TagEntry::Value(Fluid::Lava), TagEntry::Value(Fluid::FlowingLava)]});
+        pack.tag(id!(test:mobs), Tag::<Entity> {replace: false, values: &[TagEntry::Value(Entity::Cow)]});
+        pack.tag(id!(test:gems), Tag::<Item> {replace: false, values: &[TagEntry::Tag {tag: id!(c:gems), required: false}]});
+        pack.tag(id!(test:setup), Tag::<FunctionTag> {replace: false, values: &[TagEntry::Id {id: id!(othermod:setup), required: false}]});
+    });
+    assert_eq!(read(&data, "test/tags/blocks/ores.json"),
+        r##"{"replace":true,"values":["diamond_ore","#minecraft:gold_ores",{"id":"othermod:ruby_ore","required":false}]}"##);
+    assert_eq!(read(&data, "test/tags/fluids/liquids/hot.json"), r#"{"values":["lava","flowing_lava"]}"#);
+    assert_eq!(read(&data, "test/tags/entity_types/mobs.json"), r#"{"values":["cow"]}"#);
+    assert_eq!(read(&data, "test/tags/items/gems.json"), r##"{"values":[{"id":"#c:gems","required":false}]}"##);
+    assert_eq!(read(&data, "test/tags/functions/setup.json"), r#"{"values":[{"id":"othermod:setup","required":false}]}"#);
+}
+
+#[test]
+fn load_and_tick() {
+    let data = build("load_and_tick", |pack| {
+        let global = pack.objective("global", "dummy", Some("Global".into()));
+        let _ = pack.load_function(id!(test:init));
+        let mut tick = pack.tick_function(id!(test:loop/main));
+        let _ = tick.score("#x", &global) * 10;
+        let _ = pack.tick_function(Identifier::parse("test:loop/other").unwrap());
+    });
+    assert_eq!(read(&data, "minecraft/tags/functions/load.json"), r#"{"values":["copper:load","test:init"]}"#);
+    assert_eq!(read(&data, "minecraft/tags/functions/tick.json"), r#"{"values":["test:loop/main","test:loop/other"]}"#);
+    assert_eq!(read(&data, "copper/functions/load.mcfunction").lines().collect::<Vec<_>>(), [
+        r#"scoreboard objectives add global dummy {"text":"Global"}"#,
+        "scoreboard objectives add copper.const dummy",
+        "scoreboard players set #10 copper.const 10"
+    ]);
+}
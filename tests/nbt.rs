@@ -0,0 +1,262 @@
+use copper::{core::nbt::{Nbt, NbtPath, NbtPathNode, binary::{self, Compression}, ser::to_nbt}, datapack::predicate::{EntityPredicate, ItemPredicate}, prelude::*};
+use proptest::{collection::{btree_map, vec}, num::{f32, f64}, prelude::*};
+
+mod common;
+use common::*;
+
+fn nbt() -> impl Strategy<Value = Nbt> {
+    let leaf = prop_oneof![
+        any::<i8>().prop_map(Nbt::Byte),
+        any::<i16>().prop_map(Nbt::Short),
+        any::<i32>().prop_map(Nbt::Int),
+        any::<i64>().prop_map(Nbt::Long),
+        (f32::NORMAL | f32::ZERO | f32::SUBNORMAL | f32::INFINITE).prop_map(Nbt::Float),
+        (f64::NORMAL | f64::ZERO | f64::SUBNORMAL | f64::INFINITE).prop_map(Nbt::Double),
+        ".{0,8}".prop_map(Nbt::String),
+        vec(any::<i8>(), 0..4).prop_map(Nbt::ByteArray),
+        vec(any::<i32>(), 0..4).prop_map(Nbt::IntArray),
+        vec(any::<i64>(), 0..4).prop_map(Nbt::LongArray),
+        vec(any::<i32>(), 0..4).prop_map(Nbt::from),
+        vec(".{0,4}", 0..4).prop_map(Nbt::from)
+    ];
+    leaf.prop_recursive(3, 32, 4, |inner| {
+        let compound = btree_map(".{0,6}", inner, 0..4);
+        prop_oneof![
+            compound.clone().prop_map(Nbt::Compound),
+            vec(compound, 0..3).prop_map(|compounds| Nbt::List(compounds.into_iter().map(Nbt::Compound).collect()))
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn nbt_round_trip(nbt in nbt()) {
+        let string = nbt.to_string();
+        let parsed = Nbt::parse(&string).map_err(|e| TestCaseError::fail(format!("{} in `{}`", e, string)))?;
+        prop_assert_eq!(parsed, nbt);
+    }
+
+    #[test]
+    fn binary_round_trip(nbt in nbt(), name in ".{0,8}", gzip in any::<bool>()) {
+        let mut bytes = Vec::new();
+        binary::write(&mut bytes, &name, &nbt, if gzip {Compression::Gzip} else {Compression::None}).unwrap();
+        prop_assert_eq!(binary::read(&bytes[..]).unwrap(), (name, nbt));
+    }
+}
+
+#[test]
+fn snbt() {
+    assert_eq!(Nbt::from(1i8).to_string(), "1b");
+    assert_eq!(Nbt::from(true).to_string(), "1b");
+    assert_eq!(Nbt::from(-2i16).to_string(), "-2s");
+    assert_eq!(Nbt::from(3).to_string(), "3");
+    assert_eq!(Nbt::from(4i64).to_string(), "4L");
+    assert_eq!(Nbt::from(0.5f32).to_string(), "0.5f");
+    assert_eq!(Nbt::from(-1.0).to_string(), "-1d");
+    assert_eq!(Nbt::from(vec!["a", "b"]).to_string(), r#"["a","b"]"#);
+    assert_eq!(Nbt::ByteArray(vec![1, -1]).to_string(), "[B;1b,-1b]");
+    assert_eq!(Nbt::IntArray(vec![]).to_string(), "[I;]");
+    assert_eq!(Nbt::LongArray(vec![5]).to_string(), "[L;5L]");
+    assert_eq!(Nbt::from(f32::INFINITY).to_string(), "1e39f");
+    assert_eq!(Nbt::from(f64::NEG_INFINITY).to_string(), "-1e309d");
+    assert_eq!(Nbt::parse("[1e39f,-1e39F]"), Ok(Nbt::List(vec![Nbt::Float(f32::INFINITY), Nbt::Float(f32::NEG_INFINITY)])));
+    assert_eq!(Nbt::parse("[inff,NaNd]"), Ok(Nbt::from(vec!["inff", "NaNd"])));
+}
+
+#[test]
+#[should_panic(expected = "NaN can't be written as SNBT")]
+fn snbt_nan() {
+    let _ = nbt!{x: (f32::NAN)}.to_string();
+}
+
+#[test]
+#[should_panic(expected = "nbt list elements must all be the same type")]
+fn mixed_list() {
+    let _ = Nbt::List(vec![Nbt::Byte(1), Nbt::from("a")]).to_string();
+}
+
+#[test]
+fn quoting() {
+    assert_eq!(Nbt::from("plain").to_string(), r#""plain""#);
+    assert_eq!(Nbt::from(r#"say "hi""#).to_string(), r#"'say "hi"'"#);
+    assert_eq!(Nbt::from(r#"it's "both""#).to_string(), r#""it's \"both\"""#);
+    assert_eq!(Nbt::from(r"back\slash").to_string(), r#""back\\slash""#);
+    assert_eq!(nbt!{"": 1, "a b": 2, "a.b-c+d_e": 3, "quote\"": 4}.to_string(), r#"{"":1,"a b":2,a.b-c+d_e:3,'quote"':4}"#);
+}
+
+#[test]
+fn nbt_macro() {
+    let count = 3;
+    assert_eq!(nbt!{
+        a: -1,
+        b: [-1.5f32, 2.0f32,],
+        c: [{d: "e"}, {}],
+        f: [B; 1, -2],
+        g: [[L; 1], [L; count as i64]],
+        h: (count + 1),
+        i: [],
+    }.to_string(), r#"{a:-1,b:[-1.5f,2f],c:[{d:"e"},{}],f:[B;1b,-2b],g:[[L;1L],[L;3L]],h:4,i:[]}"#);
+}
+
+#[test]
+fn nbt_fields() {
+    let predicate = EntityPredicate {
+        nbt: Some(nbt!{Tags: ["boss"]}),
+        equipment: None,
+        ..default()
+    };
+    assert_eq!(serde_json::to_string(&predicate).unwrap(), r#"{"nbt":"{Tags:[\"boss\"]}"}"#);
+    let predicate = ItemPredicate {nbt: Some(nbt!{Damage: 0}), ..default()};
+    assert_eq!(serde_json::to_string(&predicate).unwrap(), r#"{"nbt":"{Damage:0}"}"#);
+}
+
+#[test]
+fn nbt_parse() {
+    assert_eq!(Nbt::parse(" { a : 1b , 'b c' : [ 1.5 , 2.0 ] , d : [I; 1, -2] } "), Ok(nbt!{a: 1i8, "b c": [1.5, 2.0], d: [I; 1, -2]}));
+    assert_eq!(Nbt::parse("[1L,2l,3S,4.5F,5D,-6,7e3,abc,true,128b]"), Err(copper::core::ParseError {
+        message: String::from("list elements must all be the same type"), position: 7
+    }));
+    assert_eq!(Nbt::parse(r#"{a:7e3,b:128b,c:true,d:1.,e:"\\\""}"#), Ok(nbt!{a: "7e3", b: "128b", c: true, d: 1.0, e: r#"\""#}));
+    assert_eq!(Nbt::parse("[B;1,2]").unwrap_err().message, "invalid element in byte array");
+    assert_eq!(Nbt::parse("{a:1,a:2}").unwrap_err().message, "duplicate key");
+    assert_eq!(Nbt::parse("{a:}").unwrap_err().position, 3);
+    assert_eq!(Nbt::parse("\"abc").unwrap_err().message, "unclosed quoted string");
+}
+
+#[test]
+fn nbt_paths() {
+    let cases = [
+        ("Inventory[{Slot:0b}].tag.display.Name", NbtPath::root("Inventory").filter(nbt!{Slot: 0i8}).key("tag").key("display").key("Name")),
+        ("{OnGround:1b}.Pos[-1]", NbtPath::compound(nbt!{OnGround: true}).key("Pos").index(-1)),
+        ("Items[].id", NbtPath::root("Items").all().key("id")),
+        (r#"a{b:"c"}[0][1]"#, NbtPath::root("a").matching(nbt!{b: "c"}).index(0).index(1)),
+        (r#""a.b"."".'"'"#, NbtPath::root("a.b").key("").key("\""))
+    ];
+    for (string, path) in cases {
+        assert_eq!(path.to_string(), string);
+        assert_eq!(NbtPath::parse(string), Ok(path));
+    }
+    assert_eq!(NbtPath::parse("").unwrap_err().message, "expected a key");
+    assert_eq!(NbtPath::parse("a.").unwrap_err().position, 2);
+    assert_eq!(NbtPath::parse("a[x]").unwrap_err(), copper::core::ParseError {message: String::from("invalid index ``"), position: 2});
+    assert_eq!(NbtPath::parse("a b").unwrap_err().message, "unexpected ` ` in nbt path");
+    assert_eq!(NbtPath::parse("a[0]{b:1}").unwrap_err().message, "unexpected `{` in nbt path");
+    assert_eq!(NbtPath::parse("[0]").unwrap_err().message, "expected a key");
+    assert_eq!(NbtPath::root("Items").all().nodes(), [NbtPathNode::Key(String::from("Items")), NbtPathNode::All]);
+}
+
+#[test]
+#[should_panic(expected = "compounds can only be matched at the root or after a key")]
+fn match_after_index() {
+    let _ = NbtPath::root("a").index(0).matching(nbt!{});
+}
+
+#[test]
+fn paths_in_commands() {
+    use copper::datapack::{function::*, item_modifier::*};
+    let path = NbtPath::parse("Inventory[{Slot:0b}].Count").unwrap();
+    let execute = Execute()
+        .store_result(StoreTarget::Storage(id!(test:vars), NbtPath::root("count"), NumericType::Int, 1.0))
+        .if_(ExecuteCondition::Data(at_s().into(), path.clone()));
+    assert_eq!(execute.to_string(), "execute store result storage test:vars count int 1 if data entity @s Inventory[{Slot:0b}].Count");
+    let operation = CopyNbtOperation {source: path, target: NbtPath::root("Count"), op: CopyNbtOperationType::Replace};
+    assert_eq!(serde_json::to_string(&operation).unwrap(), r#"{"source":"Inventory[{Slot:0b}].Count","target":"Count","op":"replace"}"#);
+    let provider: NumberProvider<'_, i64> = NumberProvider::Storage {storage: id!(test:vars), path: NbtPath::root("count")};
+    assert_eq!(serde_json::to_string(&provider).unwrap(), r#"{"type":"storage","storage":"test:vars","path":"count"}"#);
+}
+
+#[test]
+fn data_commands() {
+    use copper::{core::DataTarget, datapack::function::*};
+    assert_eq!(commands("data_commands", |_, func| {
+        let vars = || DataTarget::Storage(id!(test:vars));
+        let path = |path| NbtPath::parse(path).unwrap();
+        func.run(DataGet(vars()));
+        func.run(DataGet {path: Some((path("a.b"), None)), ..DataGet(DataTarget::Block(loc!(1 2 3)))});
+        func.run(DataGet {path: Some((path("a.b"), Some(0.5))), ..DataGet(DataTarget::Block(loc!(1 2 3)))});
+        func.run(DataMerge(at_s().into(), nbt!{NoAI: true}));
+        func.run(DataRemove(vars(), path("list[0]")));
+        func.run(DataModify(vars(), path("list"), DataModifyOperation::Append, DataModifySource::Value(nbt!{name: "x"})));
+        func.run(DataModify(vars(), path("list"), DataModifyOperation::Insert(-1), DataModifySource::From(at_s().into(), Some(path("Pos[0]")))));
+        func.run(DataModify(vars(), path("copy"), DataModifyOperation::Set, DataModifySource::From(vars(), None)));
+        let string = |slice| DataModifySource::String {source: at_s().into(), slice};
+        let custom_name = |range| Some(StringSlice {path: path("CustomName"), range});
+        func.run(DataModify(vars(), path("name"), DataModifyOperation::Set, string(None)));
+        func.run(DataModify(vars(), path("name"), DataModifyOperation::Set, string(custom_name(None))));
+        func.run(DataModify(vars(), path("name"), DataModifyOperation::Set, string(custom_name(Some((2, None))))));
+        func.run(DataModify(vars(), path("name"), DataModifyOperation::Set, string(custom_name(Some((2, Some(-2)))))));
+        func.run(DataModify(vars(), path("merged"), DataModifyOperation::Merge, DataModifySource::Value(nbt!{})));
+    }), [
+        "data get storage test:vars",
+        "data get block 1 2 3 a.b",
+        "data get block 1 2 3 a.b 0.5",
+        "data merge entity @s {NoAI:1b}",
+        "data remove storage test:vars list[0]",
+        r#"data modify storage test:vars list append value {name:"x"}"#,
+        "data modify storage test:vars list insert -1 from entity @s Pos[0]",
+        "data modify storage test:vars copy set from storage test:vars",
+        "data modify storage test:vars name set string entity @s",
+        "data modify storage test:vars name set string entity @s CustomName",
+        "data modify storage test:vars name set string entity @s CustomName 2",
+        "data modify storage test:vars name set string entity @s CustomName 2 -2",
+        "data modify storage test:vars merged merge value {}"
+    ]);
+}
+
+#[test]
+fn binary_format() {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "a", &nbt!{s: "\0\u{1f600}", l: [1i16]}, Compression::None).unwrap();
+    assert_eq!(bytes, [
+        10, 0, 1, b'a',
+        9, 0, 1, b'l', 2, 0, 0, 0, 1, 0, 1,
+        8, 0, 1, b's', 0, 8, 0xc0, 0x80, 0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80,
+        0
+    ]);
+    assert!(binary::write(Vec::new(), "", &Nbt::List(vec![1.into(), "a".into()]), Compression::None).is_err());
+    assert!(binary::read(&bytes[..bytes.len() - 1]).is_err());
+    assert!(binary::read(&[9, 0, 0, 1, 0xff, 0xff, 0xff, 0xff][..]).is_err());
+    assert!(binary::read(&[10, 0, 0, 0, 0][..]).is_err());
+}
+
+#[test]
+fn serde_to_nbt() {
+    #[derive(serde::Serialize)]
+    enum Shape {Point, Circle(f32), Rect {w: u8, h: u8}}
+    #[derive(serde::Serialize)]
+    struct Thing {
+        id: String,
+        shapes: Vec<Shape>,
+        tags: std::collections::BTreeMap<u32, bool>,
+        missing: Option<i32>,
+        nbt: Nbt
+    }
+    let thing = Thing {
+        id: "a".to_string(),
+        shapes: vec![Shape::Rect {w: 1, h: 2}],
+        tags: vec![(3, true)].into_iter().collect(),
+        missing: None,
+        nbt: nbt!{x: 1i8}
+    };
+    assert_eq!(to_nbt(&thing).unwrap(), nbt!{id: "a", shapes: [{Rect: {h: 2i16, w: 1i16}}], tags: {"3": 1i8}, nbt: {x: 1i8}});
+    assert_eq!(to_nbt(&Shape::Point).unwrap(), Nbt::from("Point"));
+    assert_eq!(to_nbt(&Shape::Circle(1.5)).unwrap(), nbt!{Circle: 1.5f32});
+    assert!(to_nbt(&(1, "a")).is_err());
+    assert!(to_nbt(&None::<i32>).is_err());
+    assert!(to_nbt(&u64::MAX).is_err());
+    assert_eq!(serde_json::to_string(&nbt!{x: 1i8}).unwrap(), r#""{x:1b}""#);
+}
+
+#[test]
+fn serde_embedded_nbt() {
+    let nbt = nbt!{
+        a: [B; 1, -2], b: [I; 1, -2], c: [L; 3], d: [[I;], [I; 4]], e: [], f: [[1i16], []],
+        g: {h: "i"}, j: (f32::INFINITY), k: (f64::NEG_INFINITY), l: 1.5f32, m: 2.5, n: 1i64
+    };
+    assert_eq!(to_nbt(&nbt).unwrap(), nbt);
+    assert_eq!(to_nbt(&vec![nbt.clone()]).unwrap(), Nbt::List(vec![nbt]));
+    match to_nbt(&nbt!{x: (f32::NAN)}).unwrap() {
+        Nbt::Compound(compound) => assert!(matches!(compound["x"], Nbt::Float(x) if x.is_nan())),
+        other => panic!("expected a compound, got {:?}", other)
+    }
+}
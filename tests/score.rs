@@ -0,0 +1,217 @@
+use copper::{core::text::{TextColor, TextComponent}, datapack::function::*, prelude::*};
+
+mod common;
+use common::*;
+
+#[test]
+fn objectives() {
+    assert_eq!(commands("objectives", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        func.run(ObjectiveAdd {display_name: Some(TextComponent::from("Kills").color(TextColor::Red)), ..ObjectiveAdd(&global, "playerKillCount")});
+        func.run(ObjectiveSetdisplay(DisplaySlot::Sidebar, &global));
+        func.run(ObjectiveSetdisplay(DisplaySlot::SidebarTeam(TextColor::Red), &global));
+        func.run(ObjectiveSetdisplay {objective: None, ..ObjectiveSetdisplay(DisplaySlot::BelowName, &global)});
+        func.run(ObjectiveModify(&global, ObjectiveModification::DisplayName(Box::new("Global".into()))));
+        func.run(ObjectiveModify(&global, ObjectiveModification::RenderType(RenderType::Hearts)));
+        func.run(ObjectiveRemove(&global));
+    }), [
+        r#"scoreboard objectives add global playerKillCount {"text":"Kills","color":"red"}"#,
+        "scoreboard objectives setdisplay sidebar global",
+        "scoreboard objectives setdisplay sidebar.team.red global",
+        "scoreboard objectives setdisplay belowName",
+        r#"scoreboard objectives modify global displayname {"text":"Global"}"#,
+        "scoreboard objectives modify global rendertype hearts",
+        "scoreboard objectives remove global"
+    ]);
+}
+
+#[test]
+fn set() {
+    assert_eq!(commands("set", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let y = func.score("#y", &global);
+        let _ = func.score("#x", &global).set_to(5).set(&y).reset().get().enable().swap(&y);
+    }), [
+        "scoreboard players set #x global 5",
+        "scoreboard players operation #x global = #y global",
+        "scoreboard players reset #x global",
+        "scoreboard players get #x global",
+        "scoreboard players enable #x global",
+        "scoreboard players operation #x global >< #y global"
+    ]);
+}
+
+#[test]
+fn comparisons() {
+    let mut conditions = Vec::new();
+    let _ = commands("comparisons", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let (x, y) = (func.score("#x", &global), func.score("#y", &global));
+        conditions = vec![
+            x.lt(&y), x.le(&y), x.gt(&y), x.ge(&y), x.eq(&y), !x.eq(&y),
+            x.eq_const(5), x.eq_const(i32::MIN.into()), x.eq_const(i32::MAX.into()),
+            x.in_range(1..=10), x.in_range(i64::from(i32::MIN)..), x.in_range(..=i64::from(i32::MAX)),
+            x.in_range(i64::from(i32::MIN)..=i64::from(i32::MAX)), !x.in_range(-5..=-1)
+        ].iter().map(ToString::to_string).collect();
+    });
+    assert_eq!(conditions, [
+        "if score #x global < #y global",
+        "if score #x global <= #y global",
+        "if score #x global > #y global",
+        "if score #x global >= #y global",
+        "if score #x global = #y global",
+        "unless score #x global = #y global",
+        "if score #x global matches 5",
+        "if score #x global matches -2147483648",
+        "if score #x global matches 2147483647",
+        "if score #x global matches 1..10",
+        "if score #x global matches -2147483648..",
+        "if score #x global matches ..2147483647",
+        "if score #x global matches -2147483648..2147483647",
+        "unless score #x global matches -5..-1"
+    ]);
+}
+
+#[test]
+fn score_operators() {
+    assert_eq!(commands("score_operators", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let y = func.score("#y", &global);
+        let x = func.score("#x", &global);
+        let _ = (x + &y - &y) * &y / &y % &y;
+        let x = func.score("#x", &global);
+        let _ = x.min(&y).max(&y);
+    }), [
+        "scoreboard players operation #x global += #y global",
+        "scoreboard players operation #x global -= #y global",
+        "scoreboard players operation #x global *= #y global",
+        "scoreboard players operation #x global /= #y global",
+        "scoreboard players operation #x global %= #y global",
+        "scoreboard players operation #x global < #y global",
+        "scoreboard players operation #x global > #y global"
+    ]);
+}
+
+#[test]
+fn constant_operators() {
+    assert_eq!(commands("constant_operators", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let x = func.score("#x", &global);
+        let _ = (x + 3 + -4 - 5 - -6 + i64::from(i32::MIN) - i64::from(i32::MIN)) * 7 / 8 % 9;
+        let x = func.score("#x", &global);
+        let _ = x.min(10).max(-10);
+    }), [
+        "scoreboard players add #x global 3",
+        "scoreboard players remove #x global 4",
+        "scoreboard players remove #x global 5",
+        "scoreboard players add #x global 6",
+        "scoreboard players operation #x global += #-2147483648 copper.const",
+        "scoreboard players operation #x global += #-2147483648 copper.const",
+        "scoreboard players operation #x global *= #7 copper.const",
+        "scoreboard players operation #x global /= #8 copper.const",
+        "scoreboard players operation #x global %= #9 copper.const",
+        "scoreboard players operation #x global < #10 copper.const",
+        "scoreboard players operation #x global > #-10 copper.const"
+    ]);
+}
+
+#[test]
+fn expressions() {
+    assert_eq!(commands("expressions", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let (a, b, c) = (func.score("#a", &global), func.score("#b", &global), func.score("#c", &global));
+        let x = func.score("#x", &global).set((&a + &b * 3) / (&c - 1));
+        let expr = &a - &x;
+        let x = x.set(expr);
+        let expr = &x + &x;
+        let _ = x.set(expr);
+    }), [
+        "scoreboard players operation #t0 copper.temp = #c global",
+        "scoreboard players remove #t0 copper.temp 1",
+        "scoreboard players operation #x global = #b global",
+        "scoreboard players operation #x global *= #3 copper.const",
+        "scoreboard players operation #x global += #a global",
+        "scoreboard players operation #x global /= #t0 copper.temp",
+        "scoreboard players operation #t0 copper.temp = #x global",
+        "scoreboard players operation #x global = #a global",
+        "scoreboard players operation #x global -= #t0 copper.temp",
+        "scoreboard players operation #x global += #x global"
+    ]);
+}
+
+#[test]
+fn temporaries() {
+    let data = build("temporaries", |pack| {
+        let global = pack.objective("global", "dummy", None);
+        let mut func = pack.function(id!(test:func));
+        let (a, b, c) = (func.score("#a", &global), func.score("#b", &global), func.score("#c", &global));
+        let _ = func.score("#x", &global).set((&a - &b) * (&c - &a % &b) + 0);
+        let _ = func.score("#y", &global).set(10 - &a * 2);
+        let _ = func.score("#z", &global).set(7);
+    });
+    assert_eq!(lines(&data, "test/functions/func.mcfunction"), [
+        "scoreboard players operation #t1 copper.temp = #a global",
+        "scoreboard players operation #t1 copper.temp %= #b global",
+        "scoreboard players operation #t0 copper.temp = #c global",
+        "scoreboard players operation #t0 copper.temp -= #t1 copper.temp",
+        "scoreboard players operation #x global = #a global",
+        "scoreboard players operation #x global -= #b global",
+        "scoreboard players operation #x global *= #t0 copper.temp",
+        "scoreboard players operation #t0 copper.temp = #a global",
+        "scoreboard players operation #t0 copper.temp *= #2 copper.const",
+        "scoreboard players set #y global 10",
+        "scoreboard players operation #y global -= #t0 copper.temp",
+        "scoreboard players set #z global 7"
+    ]);
+    assert_eq!(lines(&data, "copper/functions/load.mcfunction"), [
+        "scoreboard objectives add global dummy",
+        "scoreboard objectives add copper.temp dummy",
+        "scoreboard objectives add copper.const dummy",
+        "scoreboard players set #2 copper.const 2"
+    ]);
+}
+
+#[test]
+fn constant_pool() {
+    let data = build("constant_pool", |pack| {
+        let global = pack.objective("global", "dummy", None);
+        let mut func = pack.function(id!(test:func));
+        let (a, b) = (func.score("#a", &global), func.score("#b", &global));
+        let _ = func.score("#x", &global).set((&a * 100 + 7) % &b - i64::from(i32::MIN));
+        let _ = func.score("#y", &global).set(i64::from(i32::MIN) + &a / -3 - -5);
+        let mut other = pack.function(id!(test:other));
+        let _ = other.score("#z", &global).set(&a * 100);
+    });
+    assert_eq!(lines(&data, "test/functions/func.mcfunction"), [
+        "scoreboard players operation #x global = #a global",
+        "scoreboard players operation #x global *= #100 copper.const",
+        "scoreboard players add #x global 7",
+        "scoreboard players operation #x global %= #b global",
+        "scoreboard players operation #x global += #-2147483648 copper.const",
+        "scoreboard players operation #y global = #a global",
+        "scoreboard players operation #y global /= #-3 copper.const",
+        "scoreboard players operation #y global += #-2147483648 copper.const",
+        "scoreboard players add #y global 5"
+    ]);
+    assert_eq!(lines(&data, "test/functions/other.mcfunction"), [
+        "scoreboard players operation #z global = #a global",
+        "scoreboard players operation #z global *= #100 copper.const"
+    ]);
+    assert_eq!(lines(&data, "copper/functions/load.mcfunction"), [
+        "scoreboard objectives add global dummy",
+        "scoreboard objectives add copper.const dummy",
+        "scoreboard players set #-2147483648 copper.const -2147483648",
+        "scoreboard players set #-3 copper.const -3",
+        "scoreboard players set #100 copper.const 100"
+    ]);
+}
+
+#[test]
+#[should_panic(expected = "the constant -9223372036854775808 doesn't fit in a score")]
+fn constant_out_of_range() {
+    let _ = commands("constant_out_of_range", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let a = func.score("#a", &global);
+        let _ = func.score("#x", &global).set(&a - i64::MIN);
+    });
+}
@@ -0,0 +1,147 @@
+use std::convert::TryFrom;
+
+use copper::{core::{*, nbt::Nbt}, prelude::*};
+use proptest::{collection::vec, option, prelude::*, sample::select};
+
+type Range<N> = (Option<N>, Option<N>);
+type Criteria = Vec<(String, bool)>;
+
+/// Owned parts of a selector, which the generated selector borrows from
+#[derive(Debug)]
+struct Parts {
+    sel: usize,
+    coords: [Option<f64>; 6],
+    distance: Option<Range<f64>>,
+    scores: Vec<(String, Range<i64>)>,
+    limit: Option<u64>,
+    sort: Option<SelectorSort>,
+    level: Option<Range<u64>>,
+    game_mode: Option<(GameMode, bool)>,
+    team: Option<(String, bool)>,
+    names: Vec<(String, bool)>,
+    rotations: [Option<Range<f64>>; 2],
+    types: Vec<(Entity, bool)>,
+    tags: Vec<(String, bool)>,
+    nbt: Vec<(&'static str, bool)>,
+    advancements: Vec<(String, Option<Criteria>, bool)>,
+    predicates: Vec<(String, bool)>
+}
+impl Parts {
+    fn selector(&self) -> Selector<'_> {
+        fn range<N: Copy>((min, max): Range<N>) -> NumberRange<N> {
+            NumberRange {min, max}
+        }
+        let mut selector = [at_s, at_p, at_e, at_a, at_r][self.sel]();
+        let [x, y, z, dx, dy, dz] = self.coords;
+        selector.x = x;
+        selector.y = y;
+        selector.z = z;
+        selector.dx = dx;
+        selector.dy = dy;
+        selector.dz = dz;
+        selector.distance = self.distance.map(range);
+        selector.scores = self.scores.iter().map(|(objective, r)| (objective.as_str(), range(*r))).collect();
+        selector.limit = self.limit;
+        selector.sort = self.sort;
+        selector.level = self.level.map(range);
+        selector.game_mode = self.game_mode;
+        selector.team = self.team.as_ref().map(|(team, positive)| (team.as_str(), *positive));
+        selector.names = self.names.iter().map(|(name, positive)| (name.as_str().into(), *positive)).collect();
+        selector.x_rot = self.rotations[0].map(range);
+        selector.y_rot = self.rotations[1].map(range);
+        selector.types = self.types.clone();
+        selector.tags = self.tags.iter().map(|(tag, positive)| (tag.as_str(), *positive)).collect();
+        selector.nbt = self.nbt.iter().map(|(nbt, positive)| (Nbt::parse(nbt).unwrap(), *positive)).collect();
+        selector.advancements = self.advancements.iter().map(|(id, criteria, complete)| (
+            Identifier::parse(id).unwrap(),
+            match criteria {
+                Some(criteria) => SelectorAdvancement::Criteria(criteria.iter().map(|(c, done)| (c.as_str(), *done)).collect()),
+                None => SelectorAdvancement::Complete(*complete)
+            }
+        )).collect();
+        selector.predicates = self.predicates.iter().map(|(id, positive)| (Identifier::parse(id).unwrap(), *positive)).collect();
+        selector
+    }
+}
+
+const WORD: &str = "[a-z0-9_.+-]{0,6}";
+const ID: &str = "([a-z0-9_]{1,4}:)?[a-z0-9_]{1,4}(/[a-z0-9_]{1,4}){0,2}";
+
+fn range<N: Arbitrary + Copy>() -> impl Strategy<Value = Range<N>> {
+    (option::of(any::<N>()), option::of(any::<N>())).prop_filter("empty range", |(min, max)| min.is_some() || max.is_some())
+}
+fn float_range() -> impl Strategy<Value = Range<f64>> {
+    (option::of(-1e6..1e6), option::of(-1e6..1e6)).prop_filter("empty range", |(min, max)| min.is_some() || max.is_some())
+}
+
+prop_compose! {
+    fn parts()(
+        sel in 0..5usize,
+        coords in [option::of(-1e6..1e6), option::of(-1e6..1e6), option::of(-1e6..1e6), option::of(-1e6..1e6), option::of(-1e6..1e6), option::of(-1e6..1e6)],
+        distance in option::of(float_range()),
+        scores in vec(("[a-z0-9_.+-]{1,6}", range::<i64>()), 0..3),
+        limit in option::of(any::<u64>()),
+        sort in option::of(select(vec![SelectorSort::Nearest, SelectorSort::Furthest, SelectorSort::Arbritrary, SelectorSort::Random])),
+        level in option::of(range::<u64>()),
+        game_mode in option::of((select(vec![GameMode::Creative, GameMode::Survival, GameMode::Spectator, GameMode::Adventure]), any::<bool>())),
+        team in option::of((WORD, any::<bool>())),
+        names in vec(("\\PC{0,8}", any::<bool>()), 0..3),
+        rotations in [option::of(float_range()), option::of(float_range())],
+        types in vec((select(vec![Entity::Cow, Entity::Zombie, Entity::ArmorStand, Entity::Player]), any::<bool>()), 0..3),
+        tags in vec((WORD, any::<bool>()), 0..3),
+        nbt in vec((select(vec!["{}", "{OnGround:1b}", "{Tags:[\"a]b\",'c}d']}", "{a:{b:[1,2]}}"]), any::<bool>()), 0..3),
+        advancements in vec((ID, option::of(vec(("[a-z0-9_]{1,6}", any::<bool>()), 0..3)), any::<bool>()), 0..3),
+        predicates in vec((ID, any::<bool>()), 0..3)
+    ) -> Parts {
+        Parts {sel, coords, distance, scores, limit, sort, level, game_mode, team, names, rotations, types, tags, nbt, advancements, predicates}
+    }
+}
+
+proptest! {
+    #[test]
+    fn selector_round_trip(parts in parts()) {
+        let selector = parts.selector();
+        let string = selector.to_string();
+        let parsed = Selector::parse(&string).map_err(|e| TestCaseError::fail(format!("{} in `{}`", e, string)))?;
+        prop_assert_eq!(&parsed, &selector);
+        prop_assert_eq!(parsed.to_string(), string);
+    }
+}
+
+#[test]
+fn selector_parse() {
+    assert_eq!(
+        Selector::parse("@a[ scores = {kills=1..,deaths=..-1} , name=\"Steve the Great\" ,type=minecraft:zombie]"),
+        Ok(at_a().score("kills", 1..).score("deaths", ..=-1).name("Steve the Great", true).entity(Entity::Zombie, true))
+    );
+    assert_eq!(Selector::parse("@e[]"), Ok(at_e()));
+    let error: SelectorParseError = Selector::parse("@x").unwrap_err();
+    assert_eq!(error.position, 1);
+    assert_eq!(Selector::parse("@e[limit=1,limit=2]").unwrap_err().message, "duplicate argument `limit`");
+    assert_eq!(Selector::parse("@e[distance=..]").unwrap_err().position, 12);
+    assert_eq!(Selector::parse("@e[type=foo]").unwrap_err().message, "invalid entity type `foo`");
+    assert_eq!(Selector::parse("@e[tag=a").unwrap_err().message, "expected `,` or `]`");
+    assert_eq!(Selector::parse("@e[tag=a] ").unwrap_err().message, "unexpected characters after selector");
+    assert_eq!(Selector::parse(r#"@a[name='it\'s',name="a \\ \"b\""]"#), Ok(at_a().name("it's", true).name(r#"a \ "b""#, true)));
+    assert_eq!(Selector::parse(r#"@a[name="\n"]"#).unwrap_err().message, "invalid escape sequence");
+    assert_eq!(Selector::try_from("@e[type=cow,distance=..5,tag=!seen]"), Ok(at_e().entity(Entity::Cow, true).distance(..=5.0).tag("seen", false)));
+}
+
+#[test]
+fn selector_repeated_arguments() {
+    let selector = at_e()
+        .tag("a", true).tag("b", false).tag("", false)
+        .name("Steve", false).name("Alex", false)
+        .entity(Entity::Cow, false).entity(Entity::Zombie, false)
+        .nbt(nbt!{OnGround: true}, true).nbt(nbt!{NoAI: true}, false)
+        .score("kills", 1..).score("deaths", ..=0)
+        .advancement(id!(story/mine_stone), SelectorAdvancement::Complete(true))
+        .advancement(id!(test:quest), SelectorAdvancement::Criteria(vec![("start", true), ("end", false)]))
+        .predicate(id!(test:a), true).predicate(id!(test:b), false);
+    let string = "@e[scores={kills=1..,deaths=..0},name=!Steve,name=!Alex,type=!cow,type=!zombie,tag=a,tag=!b,tag=!,\
+        nbt={OnGround:1b},nbt=!{NoAI:1b},advancements={minecraft:story/mine_stone=true,test:quest={start=true,end=false}},\
+        predicate=test:a,predicate=!test:b]";
+    assert_eq!(selector.to_string(), string);
+    assert_eq!(Selector::parse(string), Ok(selector));
+    assert_eq!(at_a().level_range(5..).x_rotation(-90.0..=0.0).y_rotation(..=45.5).to_string(), "@a[level=5..,x_rotation=-90..0,y_rotation=..45.5]");
+}
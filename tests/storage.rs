@@ -0,0 +1,64 @@
+use copper::{core::nbt::{Nbt, NbtPath}, datapack::function::NumericType, prelude::*};
+
+mod common;
+use common::*;
+
+#[test]
+fn storage_values() {
+    assert_eq!(commands("storage_values", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let names = func.storage(id!(test:vars), NbtPath::root("names"));
+        let name = func.storage(id!(test:vars), NbtPath::parse("player.name").unwrap());
+        let score = func.score("@s", &global);
+        assert_eq!(name.path().to_string(), "player.name");
+        names.set(Nbt::List(vec![])).append(Nbt::from("Steve")).append_from(&name).remove();
+        name.set_from(&func.storage(id!(test:other), NbtPath::root("name")));
+        func.with_execute(copper::datapack::function::Execute().as_(at_a()), |f| {
+            f.storage(id!(test:vars), NbtPath::root("health"))
+                .set_from_score(&score, NumericType::Float, 0.5)
+                .copy_to_score(&score, 2.0);
+        });
+    }), [
+        "data modify storage test:vars names set value []",
+        r#"data modify storage test:vars names append value "Steve""#,
+        "data modify storage test:vars names append from storage test:vars player.name",
+        "data remove storage test:vars names",
+        "data modify storage test:vars player.name set from storage test:other name",
+        "execute as @a store result storage test:vars health float 0.5 run scoreboard players get @s global",
+        "execute as @a store result score @s global run data get storage test:vars health 2"
+    ]);
+}
+
+#[test]
+fn score_scales() {
+    assert_eq!(commands("score_scales", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let value = func.storage(id!(test:vars), NbtPath::root("value"));
+        let score = func.score("#x", &global);
+        for scale in [1.0, 0.001, 1e-7, -2.5, 1e10] {
+            value.set_from_score(&score, NumericType::Double, scale);
+        }
+        value.copy_to_score(&score, 0.1);
+    }), [
+        "execute store result storage test:vars value double 1 run scoreboard players get #x global",
+        "execute store result storage test:vars value double 0.001 run scoreboard players get #x global",
+        "execute store result storage test:vars value double 0.0000001 run scoreboard players get #x global",
+        "execute store result storage test:vars value double -2.5 run scoreboard players get #x global",
+        "execute store result storage test:vars value double 10000000000 run scoreboard players get #x global",
+        "execute store result score #x global run data get storage test:vars value 0.1"
+    ]);
+}
+
+#[test]
+fn score_round_trip() {
+    assert_eq!(commands("score_round_trip", |pack, func| {
+        let global = pack.objective("global", "dummy", None);
+        let speed = func.storage(id!(test:vars), NbtPath::root("speed"));
+        let score = func.score("#speed", &global);
+        let copy = func.score("#copy", &global);
+        speed.set_from_score(&score, NumericType::Float, 0.01).copy_to_score(&copy, 100.0);
+    }), [
+        "execute store result storage test:vars speed float 0.01 run scoreboard players get #speed global",
+        "execute store result score #copy global run data get storage test:vars speed 100"
+    ]);
+}
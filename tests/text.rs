@@ -0,0 +1,26 @@
+use copper::{core::{DataTarget, ScoreHolder, nbt::NbtPath, text::*}, prelude::*};
+
+#[test]
+fn text_components() {
+    let component = TextComponent::translate("chat.type.text", vec![
+        TextComponent::score(ScoreHolder::from("@s"), "kills").color(TextColor::Hex(0xff8800)),
+        TextComponent::keybind("key.jump").italic(false).underlined(true).strikethrough(true).obfuscated(false)
+    ])
+        .font(id!(uniform))
+        .insertion("hi")
+        .hover(HoverEvent::ShowItem {id: Item::Diamond, count: Some(2), tag: Some(nbt!{Damage: 5})})
+        .extra(TextComponent::nbt(NbtPath::root("Inventory").index(0), at_p().into(), true)
+            .hover(HoverEvent::ShowText(Box::new("tip".into())))
+            .click(ClickEvent::ChangePage(2)))
+        .extra(TextComponent::nbt(NbtPath::root("name"), DataTarget::Storage(id!(test:vars)), false))
+        .extra(TextComponent::text("x").hover(HoverEvent::ShowEntity {ty: Entity::Cow, id: "0-0-0-0-1", name: Some(Box::new("Bessie".into()))}));
+    assert_eq!(component.to_string(), concat!(
+        r##"{"translate":"chat.type.text","with":[{"score":{"name":"@s","objective":"kills"},"color":"#ff8800"},"##,
+        r#"{"keybind":"key.jump","italic":false,"underlined":true,"strikethrough":true,"obfuscated":false}],"#,
+        r#""font":"minecraft:uniform","insertion":"hi","hoverEvent":{"action":"show_item","contents":{"id":"diamond","count":2,"tag":"{Damage:5}"}},"#,
+        r#""extra":[{"nbt":"Inventory[0]","entity":"@p","interpret":true,"clickEvent":{"action":"change_page","value":2},"#,
+        r#""hoverEvent":{"action":"show_text","contents":{"text":"tip"}}},"#,
+        r#"{"nbt":"name","storage":"test:vars"},"#,
+        r#"{"text":"x","hoverEvent":{"action":"show_entity","contents":{"type":"cow","id":"0-0-0-0-1","name":{"text":"Bessie"}}}}]}"#
+    ));
+}